@@ -0,0 +1,26 @@
+use super::Nutrition;
+use chrono::NaiveDate;
+
+/// Whether a diary entry was logged from a recipe or a single ingredient
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FoodKind {
+    /// A recipe, possibly in a portion
+    Recipe,
+    /// A single ingredient by weight
+    Ingredient,
+}
+
+/// Food eaten on a day, with its nutrition as it was when logged
+#[derive(Debug, Clone)]
+pub struct DiaryEntry {
+    /// Day the food was eaten
+    pub date: NaiveDate,
+    /// Meal label, e.g. "Lunch"
+    pub meal: Option<String>,
+    /// Recipe or ingredient name
+    pub food: String,
+    /// Whether `food` names a recipe or an ingredient
+    pub kind: FoodKind,
+    /// Nutrition of the amount eaten
+    pub nutrition: Nutrition,
+}
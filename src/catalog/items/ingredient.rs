@@ -1,10 +1,33 @@
+use super::{Nutrition, WeightedIngredient};
+
+/// A food with its nutrient values per 100 g, as listed in ingredients.jsonc
 #[derive(Debug, Clone)]
 pub struct Ingredient {
+    /// Stable identifier referenced from recipes
+    pub id: String,
+    /// Display name, e.g. "Brown Rice (cooked)"
     pub name: String,
     /// Net carbohydrates per 100 grams (total carbs minus fiber)
     pub carbs_per_100g: f64,
+    /// Protein per 100 grams
     pub protein_per_100g: f64,
+    /// Fat per 100 grams
     pub fat_per_100g: f64,
     /// Dietary fiber per 100 grams (in grams)
     pub fiber_per_100g: f64,
+    /// Store section used to group shopping lists
+    pub aisle: Option<String>,
+    /// Weight of one package as sold, used to round shopping lists up
+    pub package_grams: Option<f64>,
+}
+
+impl Ingredient {
+    /// Nutrition for the given amount of this ingredient
+    pub fn nutrition_for(&self, grams: f64) -> Nutrition {
+        WeightedIngredient {
+            grams,
+            ingredient: self.clone(),
+        }
+        .nutrition()
+    }
 }
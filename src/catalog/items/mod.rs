@@ -1,11 +1,84 @@
+//! The catalog's data: ingredients, recipes and what is computed from them
+
+mod diary;
 mod ingredient;
+mod nutrition;
+mod pantry;
+mod plan;
+mod targets;
 mod weighted_ingredient;
 
+pub use diary::{DiaryEntry, FoodKind};
 pub use ingredient::Ingredient;
+pub use nutrition::{EnergySplit, Nutrition};
+pub use pantry::{Pantry, PantryItem};
+pub use plan::{DayPlan, PlannedMeal, Portion};
+pub use targets::{DailyTargets, Nutrient, NutrientTarget, TargetStatus};
 pub use weighted_ingredient::WeightedIngredient;
 
+/// A named list of weighed ingredients, as listed in recipes.jsonc
 #[derive(Debug, Clone)]
 pub struct Recipe {
+    /// Unique name, e.g. "Chicken Rice Bowl"
     pub name: String,
+    /// Free-form labels for grouping and filtering (e.g. "breakfast")
+    pub tags: Vec<String>,
+    /// Ingredients with their amounts, in file order
     pub ingredients: Vec<WeightedIngredient>,
+    /// Weight of the finished dish, when cooking changes it
+    pub cooked_grams: Option<f64>,
+}
+
+impl Recipe {
+    /// Sum of the nutrition of all ingredients
+    pub fn totals(&self) -> Nutrition {
+        self.ingredients.iter().map(|i| i.nutrition()).sum()
+    }
+
+    /// Weight of the finished dish: the cooked weight if given, otherwise the ingredient weights
+    pub fn finished_grams(&self) -> f64 {
+        self.cooked_grams.unwrap_or_else(|| self.totals().grams)
+    }
+
+    /// Nutrition per 100 g of the finished dish, if the recipe has any weight
+    ///
+    /// The `grams` of the result are 100.
+    pub fn per_100g(&self) -> Option<Nutrition> {
+        let finished = self.finished_grams();
+        (finished > 0.0).then(|| {
+            let mut per_100g = self.totals() * (100.0 / finished);
+            per_100g.grams = 100.0;
+            per_100g
+        })
+    }
+
+    /// Multiple of the recipe that makes up a portion of the finished dish
+    pub fn factor_for_portion(&self, grams: f64) -> f64 {
+        let finished = self.finished_grams();
+        if finished > 0.0 {
+            grams / finished
+        } else {
+            0.0
+        }
+    }
+
+    /// The recipe with every ingredient amount multiplied by `factor`
+    pub fn scaled(&self, factor: f64) -> Recipe {
+        Recipe {
+            name: self.name.clone(),
+            tags: self.tags.clone(),
+            ingredients: self.ingredients.iter().map(|i| i.scaled(factor)).collect(),
+            cooked_grams: self.cooked_grams.map(|grams| grams * factor),
+        }
+    }
+
+    /// Whether the recipe has this tag (ignoring ASCII case)
+    pub fn has_tag(&self, tag: &str) -> bool {
+        self.tags.iter().any(|t| t.eq_ignore_ascii_case(tag))
+    }
+
+    /// Whether the recipe contains the ingredient with this ID
+    pub fn uses_ingredient(&self, id: &str) -> bool {
+        self.ingredients.iter().any(|i| i.ingredient.id == id)
+    }
 }
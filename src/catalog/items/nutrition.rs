@@ -0,0 +1,87 @@
+use std::ops::{Add, AddAssign, Mul};
+
+/// Nutrient amounts for a weighed quantity of food.
+///
+/// "Carbs" refers to net carbohydrates (excluding fiber).
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Nutrition {
+    /// Weight of the food
+    pub grams: f64,
+    /// Net carbohydrates, in grams
+    pub carbs: f64,
+    /// Protein, in grams
+    pub protein: f64,
+    /// Fat, in grams
+    pub fat: f64,
+    /// Dietary fiber, in grams
+    pub fiber: f64,
+    /// Energy in kcal (4 per gram of protein and net carbs, 9 per gram of fat)
+    pub calories: f64,
+}
+
+/// Fractions of total calories contributed by each macronutrient
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EnergySplit {
+    /// Share from protein, between 0 and 1
+    pub protein: f64,
+    /// Share from fat, between 0 and 1
+    pub fat: f64,
+    /// Share from net carbs, between 0 and 1
+    pub carbs: f64,
+}
+
+impl Nutrition {
+    /// Where the calories come from, or `None` if there are no calories
+    pub fn energy_split(&self) -> Option<EnergySplit> {
+        if self.calories <= 0.0 {
+            return None;
+        }
+        Some(EnergySplit {
+            protein: self.protein * 4.0 / self.calories,
+            fat: self.fat * 9.0 / self.calories,
+            carbs: self.carbs * 4.0 / self.calories,
+        })
+    }
+}
+
+impl Add for Nutrition {
+    type Output = Nutrition;
+
+    fn add(self, other: Nutrition) -> Nutrition {
+        Nutrition {
+            grams: self.grams + other.grams,
+            carbs: self.carbs + other.carbs,
+            protein: self.protein + other.protein,
+            fat: self.fat + other.fat,
+            fiber: self.fiber + other.fiber,
+            calories: self.calories + other.calories,
+        }
+    }
+}
+
+impl AddAssign for Nutrition {
+    fn add_assign(&mut self, other: Nutrition) {
+        *self = *self + other;
+    }
+}
+
+impl Mul<f64> for Nutrition {
+    type Output = Nutrition;
+
+    fn mul(self, factor: f64) -> Nutrition {
+        Nutrition {
+            grams: self.grams * factor,
+            carbs: self.carbs * factor,
+            protein: self.protein * factor,
+            fat: self.fat * factor,
+            fiber: self.fiber * factor,
+            calories: self.calories * factor,
+        }
+    }
+}
+
+impl std::iter::Sum for Nutrition {
+    fn sum<I: Iterator<Item = Nutrition>>(iter: I) -> Nutrition {
+        iter.fold(Nutrition::default(), Add::add)
+    }
+}
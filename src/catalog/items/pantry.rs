@@ -0,0 +1,69 @@
+use super::WeightedIngredient;
+
+/// Stock of one ingredient
+#[derive(Debug, Clone, PartialEq)]
+pub struct PantryItem {
+    /// Ingredient ID from the catalog
+    pub id: String,
+    /// Amount in stock
+    pub grams: f64,
+    /// Stock at or below this amount counts as running low
+    pub low_grams: Option<f64>,
+}
+
+impl PantryItem {
+    /// Empty, or at or below its low mark
+    pub fn is_low(&self) -> bool {
+        self.grams <= 0.0 || self.low_grams.is_some_and(|low| self.grams <= low)
+    }
+}
+
+/// Current stock per ingredient, in file order
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Pantry {
+    /// Stock entries, one per ingredient
+    pub items: Vec<PantryItem>,
+}
+
+impl Pantry {
+    /// Grams in stock; ingredients not in the pantry have none
+    pub fn stock(&self, id: &str) -> f64 {
+        self.items
+            .iter()
+            .find(|item| item.id == id)
+            .map_or(0.0, |item| item.grams)
+    }
+
+    /// Whole batches of the given amounts that current stock covers
+    pub fn batches(&self, ingredients: &[WeightedIngredient]) -> u32 {
+        total_by_id(ingredients)
+            .into_iter()
+            .filter(|(_, needed)| *needed > 0.0)
+            .map(|(id, needed)| (self.stock(&id) / needed + 1e-9).floor())
+            .fold(f64::INFINITY, f64::min)
+            .min(u32::MAX as f64) as u32
+    }
+
+    /// Missing grams per ingredient ID for cooking the given amounts, in first-use order
+    pub fn shortfalls(&self, ingredients: &[WeightedIngredient]) -> Vec<(String, f64)> {
+        total_by_id(ingredients)
+            .into_iter()
+            .filter_map(|(id, needed)| {
+                let missing = needed - self.stock(&id);
+                (missing > 1e-9).then_some((id, missing))
+            })
+            .collect()
+    }
+}
+
+/// Grams per ingredient ID, with repeated ingredients added together
+fn total_by_id(ingredients: &[WeightedIngredient]) -> Vec<(String, f64)> {
+    let mut totals: Vec<(String, f64)> = Vec::new();
+    for entry in ingredients {
+        match totals.iter_mut().find(|(id, _)| *id == entry.ingredient.id) {
+            Some((_, grams)) => *grams += entry.grams,
+            None => totals.push((entry.ingredient.id.clone(), entry.grams)),
+        }
+    }
+    totals
+}
@@ -0,0 +1,57 @@
+use super::{Nutrition, Recipe, WeightedIngredient};
+
+/// A named day of meals made from catalog recipes
+#[derive(Debug, Clone)]
+pub struct DayPlan {
+    /// Day label, e.g. "Monday"
+    pub name: String,
+    /// Meals in file order
+    pub meals: Vec<PlannedMeal>,
+}
+
+/// A recipe eaten at a meal, in a given portion
+#[derive(Debug, Clone)]
+pub struct PlannedMeal {
+    /// Meal label, e.g. "Breakfast"
+    pub meal: String,
+    /// The catalog recipe as written
+    pub recipe: Recipe,
+    /// How much of the recipe is eaten
+    pub portion: Portion,
+}
+
+/// How much of a recipe a meal is made of
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Portion {
+    /// Multiple of the recipe as written
+    Multiplier(f64),
+    /// Total weight eaten, in grams
+    Grams(f64),
+}
+
+impl PlannedMeal {
+    /// Factor applied to the recipe's ingredient amounts
+    pub fn factor(&self) -> f64 {
+        match self.portion {
+            Portion::Multiplier(multiplier) => multiplier,
+            Portion::Grams(grams) => self.recipe.factor_for_portion(grams),
+        }
+    }
+
+    /// The recipe's ingredients in the planned amounts
+    pub fn ingredients(&self) -> Vec<WeightedIngredient> {
+        self.recipe.scaled(self.factor()).ingredients
+    }
+
+    /// Nutrition of the planned amounts
+    pub fn nutrition(&self) -> Nutrition {
+        self.ingredients().iter().map(|i| i.nutrition()).sum()
+    }
+}
+
+impl DayPlan {
+    /// Sum of the nutrition of all meals
+    pub fn totals(&self) -> Nutrition {
+        self.meals.iter().map(|m| m.nutrition()).sum()
+    }
+}
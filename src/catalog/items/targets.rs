@@ -0,0 +1,107 @@
+use super::Nutrition;
+
+/// A nutrient that daily targets can be set for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Nutrient {
+    /// Net carbohydrates, in grams
+    Carbs,
+    /// Protein, in grams
+    Protein,
+    /// Fat, in grams
+    Fat,
+    /// Dietary fiber, in grams
+    Fiber,
+    /// Energy, in kcal
+    Calories,
+}
+
+impl Nutrient {
+    /// All nutrients in table column order
+    pub const ALL: [Nutrient; 5] = [
+        Nutrient::Carbs,
+        Nutrient::Protein,
+        Nutrient::Fat,
+        Nutrient::Fiber,
+        Nutrient::Calories,
+    ];
+
+    /// This nutrient's amount in `nutrition`
+    pub fn value(&self, nutrition: &Nutrition) -> f64 {
+        match self {
+            Nutrient::Carbs => nutrition.carbs,
+            Nutrient::Protein => nutrition.protein,
+            Nutrient::Fat => nutrition.fat,
+            Nutrient::Fiber => nutrition.fiber,
+            Nutrient::Calories => nutrition.calories,
+        }
+    }
+
+    /// Name shown in tables, e.g. "Net carbs"
+    pub fn label(&self) -> &'static str {
+        match self {
+            Nutrient::Carbs => "Net carbs",
+            Nutrient::Protein => "Protein",
+            Nutrient::Fat => "Fat",
+            Nutrient::Fiber => "Fiber",
+            Nutrient::Calories => "Calories",
+        }
+    }
+}
+
+/// Daily minimum, maximum, or both (a range) for one nutrient
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NutrientTarget {
+    /// Least amount to reach in a day
+    pub min: Option<f64>,
+    /// Most amount to stay under in a day
+    pub max: Option<f64>,
+}
+
+/// Where a daily amount stands relative to its target
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TargetStatus {
+    /// Below the minimum by this much
+    Under(f64),
+    /// Within the target, with this much left before the maximum (if any)
+    Within {
+        /// Amount left before the maximum
+        left: Option<f64>,
+    },
+    /// Above the maximum by this much
+    Over(f64),
+}
+
+impl NutrientTarget {
+    /// Amount that 100% refers to: the maximum if there is one, otherwise the minimum
+    pub fn reference(&self) -> Option<f64> {
+        self.max.or(self.min)
+    }
+
+    /// Where `value` stands relative to this target
+    pub fn status(&self, value: f64) -> TargetStatus {
+        match (self.min, self.max) {
+            (Some(min), _) if value < min => TargetStatus::Under(min - value),
+            (_, Some(max)) if value > max => TargetStatus::Over(value - max),
+            (_, max) => TargetStatus::Within {
+                left: max.map(|max| max - value),
+            },
+        }
+    }
+}
+
+/// Targets for one day, for the nutrients that have one
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DailyTargets {
+    /// In table column order
+    pub targets: Vec<(Nutrient, NutrientTarget)>,
+}
+
+impl DailyTargets {
+    /// The target for `nutrient`, if one is set
+    pub fn get(&self, nutrient: Nutrient) -> Option<&NutrientTarget> {
+        self.targets
+            .iter()
+            .find(|(n, _)| *n == nutrient)
+            .map(|(_, target)| target)
+    }
+}
@@ -1,13 +1,15 @@
-use super::Ingredient;
+use super::{Ingredient, Nutrition};
+
+const PER_100G_FACTOR: f64 = 0.01;
 
 /// Ingredient along with its weight in grams.
 ///
 /// "Carbs" fields refer to net carbohydrates (excluding fiber).
-const PER_100G_FACTOR: f64 = 0.01;
-
 #[derive(Debug, Clone)]
 pub struct WeightedIngredient {
+    /// Amount of the ingredient
     pub grams: f64,
+    /// The ingredient and its values per 100 g
     pub ingredient: Ingredient,
 }
 
@@ -19,10 +21,12 @@ impl WeightedIngredient {
         self.grams * self.ingredient.carbs_per_100g * PER_100G_FACTOR
     }
 
+    /// Protein in grams for the weighted ingredient
     pub fn protein_grams(&self) -> f64 {
         self.grams * self.ingredient.protein_per_100g * PER_100G_FACTOR
     }
 
+    /// Fat in grams for the weighted ingredient
     pub fn fat_grams(&self) -> f64 {
         self.grams * self.ingredient.fat_per_100g * PER_100G_FACTOR
     }
@@ -31,7 +35,28 @@ impl WeightedIngredient {
         self.grams * self.ingredient.fiber_per_100g * PER_100G_FACTOR
     }
 
+    /// Energy in kcal, from protein, fat and net carbs
     pub fn calories(&self) -> f64 {
         self.protein_grams() * 4.0 + self.fat_grams() * 9.0 + self.carbs_grams() * 4.0
     }
+
+    /// The same ingredient with its weight multiplied by `factor`
+    pub fn scaled(&self, factor: f64) -> WeightedIngredient {
+        WeightedIngredient {
+            grams: self.grams * factor,
+            ingredient: self.ingredient.clone(),
+        }
+    }
+
+    /// All nutrient amounts for the weighted ingredient
+    pub fn nutrition(&self) -> Nutrition {
+        Nutrition {
+            grams: self.grams,
+            carbs: self.carbs_grams(),
+            protein: self.protein_grams(),
+            fat: self.fat_grams(),
+            fiber: self.fiber_grams(),
+            calories: self.calories(),
+        }
+    }
 }
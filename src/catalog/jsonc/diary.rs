@@ -0,0 +1,130 @@
+use super::initialization::create_diary_schema;
+use super::loader::{parse_jsonc_content, read_catalog_file};
+use crate::catalog::items::{DiaryEntry, FoodKind, Nutrition};
+use crate::error::AppError;
+use chrono::NaiveDate;
+use jsonc_parser::ParseOptions;
+use jsonc_parser::cst::{CstInputValue, CstRootNode};
+use serde::Deserialize;
+use std::path::Path;
+
+const DIARY_FILE: &str = "diary.jsonc";
+const DIARY_TEMPLATE: &str = include_str!("diary.template.jsonc");
+const DATE_FORMAT: &str = "%Y-%m-%d";
+
+#[derive(Deserialize)]
+struct JsonDiary {
+    entries: Vec<JsonDiaryEntry>,
+}
+
+#[derive(Deserialize)]
+struct JsonDiaryEntry {
+    date: String,
+    meal: Option<String>,
+    recipe: Option<String>,
+    ingredient: Option<String>,
+    grams: f64,
+    carbs: f64,
+    protein: f64,
+    fat: f64,
+    fiber: f64,
+    calories: f64,
+}
+
+/// Load all diary entries in file order; a missing diary has no entries
+pub fn load_diary(data_dir: &Path) -> Result<Vec<DiaryEntry>, AppError> {
+    if !data_dir.join(DIARY_FILE).exists() {
+        return Ok(Vec::new());
+    }
+    let content = read_catalog_file(data_dir, DIARY_FILE)?;
+    let json_diary: JsonDiary = parse_jsonc_content(&content, DIARY_FILE, create_diary_schema)?;
+    json_diary.entries.into_iter().map(build_entry).collect()
+}
+
+/// Append an entry to the diary, creating the file if needed and keeping its comments
+pub fn append_diary_entry(data_dir: &Path, entry: &DiaryEntry) -> Result<(), AppError> {
+    let path = data_dir.join(DIARY_FILE);
+    let content = if path.exists() {
+        read_catalog_file(data_dir, DIARY_FILE)?
+    } else {
+        DIARY_TEMPLATE.to_string()
+    };
+
+    let root = CstRootNode::parse(&content, &ParseOptions::default()).map_err(|e| {
+        AppError::ParsingError {
+            message: format!("Invalid JSONC syntax in {}: {}", DIARY_FILE, e),
+        }
+    })?;
+    let entries = root
+        .object_value()
+        .and_then(|object| object.array_value("entries"))
+        .ok_or_else(|| AppError::InvalidEdit {
+            message: format!("{} has no \"entries\" array", DIARY_FILE),
+        })?;
+    entries.append(entry_value(entry));
+
+    let updated = root.to_string();
+    parse_jsonc_content::<JsonDiary>(&updated, DIARY_FILE, create_diary_schema)?;
+    std::fs::write(path, updated)?;
+    Ok(())
+}
+
+fn build_entry(json: JsonDiaryEntry) -> Result<DiaryEntry, AppError> {
+    let date = NaiveDate::parse_from_str(&json.date, DATE_FORMAT).map_err(|e| {
+        AppError::TypeMappingError {
+            message: format!("Invalid date '{}' in {}: {}", json.date, DIARY_FILE, e),
+        }
+    })?;
+    let (food, kind) = match (json.recipe, json.ingredient) {
+        (Some(recipe), _) => (recipe, FoodKind::Recipe),
+        (None, Some(ingredient)) => (ingredient, FoodKind::Ingredient),
+        (None, None) => unreachable!("the diary schema requires a recipe or an ingredient"),
+    };
+    Ok(DiaryEntry {
+        date,
+        meal: json.meal,
+        food,
+        kind,
+        nutrition: Nutrition {
+            grams: json.grams,
+            carbs: json.carbs,
+            protein: json.protein,
+            fat: json.fat,
+            fiber: json.fiber,
+            calories: json.calories,
+        },
+    })
+}
+
+fn entry_value(entry: &DiaryEntry) -> CstInputValue {
+    let mut properties = vec![(
+        "date".to_string(),
+        CstInputValue::String(entry.date.format(DATE_FORMAT).to_string()),
+    )];
+    if let Some(meal) = &entry.meal {
+        properties.push(("meal".to_string(), CstInputValue::String(meal.clone())));
+    }
+    let kind = match entry.kind {
+        FoodKind::Recipe => "recipe",
+        FoodKind::Ingredient => "ingredient",
+    };
+    properties.push((kind.to_string(), CstInputValue::String(entry.food.clone())));
+
+    let nutrition = &entry.nutrition;
+    for (key, value) in [
+        ("grams", nutrition.grams),
+        ("carbs", nutrition.carbs),
+        ("protein", nutrition.protein),
+        ("fat", nutrition.fat),
+        ("fiber", nutrition.fiber),
+        ("calories", nutrition.calories),
+    ] {
+        properties.push((key.to_string(), rounded_number(value)));
+    }
+    CstInputValue::Object(properties)
+}
+
+/// Two decimals are plenty for logged amounts and keep the file readable
+pub(super) fn rounded_number(value: f64) -> CstInputValue {
+    CstInputValue::Number(((value * 100.0).round() / 100.0).to_string())
+}
@@ -0,0 +1,346 @@
+use super::formatter::{SortRule, format_jsonc};
+use super::loader::{INGREDIENTS_FILE, RECIPES_FILE, load_catalog_from_str, read_catalog_file};
+use super::pantry::{PANTRY_FILE, check_pantry, rename_pantry_ingredient};
+use crate::catalog::items::Ingredient;
+use crate::catalog::{Edit, FileChange};
+use crate::error::AppError;
+use jsonc_parser::ParseOptions;
+use jsonc_parser::cst::{CstArray, CstInputValue, CstObject, CstRootNode};
+use std::path::Path;
+
+/// Work out the file changes for an edit without writing anything
+///
+/// The edited contents must load as a complete catalog, otherwise the edit is rejected.
+/// Renaming an ingredient also renames it in the pantry, which must then still match
+/// the catalog.
+pub fn plan_edit(data_dir: &Path, edit: &Edit) -> Result<Vec<FileChange>, AppError> {
+    let recipes = read_catalog_file(data_dir, RECIPES_FILE)?;
+    let ingredients = read_catalog_file(data_dir, INGREDIENTS_FILE)?;
+    let pantry = if data_dir.join(PANTRY_FILE).exists() {
+        read_catalog_file(data_dir, PANTRY_FILE)?
+    } else {
+        String::new()
+    };
+
+    let (updated_recipes, updated_ingredients) = match edit {
+        Edit::AddIngredient(ingredient) => {
+            (recipes.clone(), add_ingredient(&ingredients, ingredient)?)
+        }
+        Edit::AddRecipe {
+            name,
+            tags,
+            ingredients: amounts,
+        } => (
+            add_recipe(&recipes, name, tags, amounts)?,
+            ingredients.clone(),
+        ),
+        Edit::SetRecipeIngredient {
+            recipe,
+            ingredient_id,
+            grams,
+        } => (
+            set_recipe_ingredient(&recipes, recipe, ingredient_id, *grams)?,
+            ingredients.clone(),
+        ),
+        Edit::RemoveRecipeIngredient {
+            recipe,
+            ingredient_id,
+        } => (
+            remove_recipe_ingredient(&recipes, recipe, ingredient_id)?,
+            ingredients.clone(),
+        ),
+        Edit::RenameIngredient { old_id, new_id } => {
+            rename_ingredient(&recipes, &ingredients, old_id, new_id)?
+        }
+        Edit::Format(options) => (
+            format_jsonc(
+                &recipes,
+                RECIPES_FILE,
+                options.sort_recipes.then_some(SortRule {
+                    array: "recipes",
+                    key: "name",
+                }),
+                options.normalize_numbers,
+            )?,
+            format_jsonc(
+                &ingredients,
+                INGREDIENTS_FILE,
+                options.sort_ingredients.then_some(SortRule {
+                    array: "ingredients",
+                    key: "id",
+                }),
+                options.normalize_numbers,
+            )?,
+        ),
+    };
+
+    let catalog = load_catalog_from_str(&updated_recipes, &updated_ingredients).map_err(|e| {
+        invalid_edit(format!(
+            "Refusing to write changes that would leave the catalog unloadable:\n\n{}",
+            e
+        ))
+    })?;
+
+    let updated_pantry = match edit {
+        Edit::RenameIngredient { old_id, new_id } if !pantry.is_empty() => {
+            rename_pantry_ingredient(&pantry, old_id, new_id)?
+        }
+        _ => pantry.clone(),
+    };
+    if updated_pantry != pantry {
+        check_pantry(&updated_pantry, &catalog).map_err(|e| {
+            invalid_edit(format!(
+                "Refusing to write changes that would leave the pantry unloadable:\n\n{}",
+                e
+            ))
+        })?;
+    }
+
+    let mut changes = Vec::new();
+    for (filename, original, updated) in [
+        (RECIPES_FILE, recipes, updated_recipes),
+        (INGREDIENTS_FILE, ingredients, updated_ingredients),
+        (PANTRY_FILE, pantry, updated_pantry),
+    ] {
+        if original != updated {
+            changes.push(FileChange {
+                path: data_dir.join(filename),
+                original,
+                updated,
+            });
+        }
+    }
+    Ok(changes)
+}
+
+fn add_ingredient(content: &str, ingredient: &Ingredient) -> Result<String, AppError> {
+    let root = parse_cst(content, INGREDIENTS_FILE)?;
+    let array = top_level_array(&root, "ingredients", INGREDIENTS_FILE)?;
+    if find_object(&array, "id", &ingredient.id).is_some() {
+        return Err(invalid_edit(format!(
+            "Ingredient '{}' already exists in {}",
+            ingredient.id, INGREDIENTS_FILE
+        )));
+    }
+    let mut properties = vec![
+        ("id".to_string(), string_value(&ingredient.id)),
+        ("name".to_string(), string_value(&ingredient.name)),
+        (
+            "carbs_per_100g".to_string(),
+            number_value(ingredient.carbs_per_100g),
+        ),
+        (
+            "protein_per_100g".to_string(),
+            number_value(ingredient.protein_per_100g),
+        ),
+        (
+            "fat_per_100g".to_string(),
+            number_value(ingredient.fat_per_100g),
+        ),
+        (
+            "fiber_per_100g".to_string(),
+            number_value(ingredient.fiber_per_100g),
+        ),
+    ];
+    if let Some(aisle) = &ingredient.aisle {
+        properties.push(("aisle".to_string(), string_value(aisle)));
+    }
+    if let Some(grams) = ingredient.package_grams {
+        properties.push(("package_grams".to_string(), number_value(grams)));
+    }
+    array.append(CstInputValue::Object(properties));
+    Ok(root.to_string())
+}
+
+fn add_recipe(
+    content: &str,
+    name: &str,
+    tags: &[String],
+    amounts: &[(String, f64)],
+) -> Result<String, AppError> {
+    let root = parse_cst(content, RECIPES_FILE)?;
+    let array = top_level_array(&root, "recipes", RECIPES_FILE)?;
+    if find_object(&array, "name", name).is_some() {
+        return Err(invalid_edit(format!(
+            "Recipe '{}' already exists in {}",
+            name, RECIPES_FILE
+        )));
+    }
+    let mut properties = vec![("name".to_string(), string_value(name))];
+    if !tags.is_empty() {
+        properties.push((
+            "tags".to_string(),
+            CstInputValue::Array(tags.iter().map(|tag| string_value(tag)).collect()),
+        ));
+    }
+    properties.push((
+        "ingredients".to_string(),
+        CstInputValue::Array(
+            amounts
+                .iter()
+                .map(|(id, grams)| recipe_ingredient_value(id, *grams))
+                .collect(),
+        ),
+    ));
+    array.append(CstInputValue::Object(properties));
+    Ok(root.to_string())
+}
+
+fn set_recipe_ingredient(
+    content: &str,
+    recipe: &str,
+    ingredient_id: &str,
+    grams: f64,
+) -> Result<String, AppError> {
+    let root = parse_cst(content, RECIPES_FILE)?;
+    let recipe_ingredients = recipe_ingredients_array(&root, recipe)?;
+    let mut existing = matching_objects(&recipe_ingredients, "id", ingredient_id);
+    if existing.is_empty() {
+        recipe_ingredients.append(recipe_ingredient_value(ingredient_id, grams));
+    } else {
+        // Collapse repeated entries so the recipe ends up with exactly the requested amount
+        let first = existing.remove(0);
+        match first.get("grams") {
+            Some(prop) => prop.set_value(number_value(grams)),
+            None => {
+                first.append("grams", number_value(grams));
+            }
+        }
+        for duplicate in existing {
+            duplicate.remove();
+        }
+    }
+    Ok(root.to_string())
+}
+
+fn remove_recipe_ingredient(
+    content: &str,
+    recipe: &str,
+    ingredient_id: &str,
+) -> Result<String, AppError> {
+    let root = parse_cst(content, RECIPES_FILE)?;
+    let recipe_ingredients = recipe_ingredients_array(&root, recipe)?;
+    let existing = matching_objects(&recipe_ingredients, "id", ingredient_id);
+    if existing.is_empty() {
+        return Err(invalid_edit(format!(
+            "Recipe '{}' does not use ingredient '{}'",
+            recipe, ingredient_id
+        )));
+    }
+    for object in existing {
+        object.remove();
+    }
+    Ok(root.to_string())
+}
+
+fn rename_ingredient(
+    recipes_content: &str,
+    ingredients_content: &str,
+    old_id: &str,
+    new_id: &str,
+) -> Result<(String, String), AppError> {
+    let ingredients_root = parse_cst(ingredients_content, INGREDIENTS_FILE)?;
+    let ingredients = top_level_array(&ingredients_root, "ingredients", INGREDIENTS_FILE)?;
+    if find_object(&ingredients, "id", new_id).is_some() {
+        return Err(invalid_edit(format!(
+            "Cannot rename '{}': ingredient '{}' already exists in {}",
+            old_id, new_id, INGREDIENTS_FILE
+        )));
+    }
+    let definition = find_object(&ingredients, "id", old_id).ok_or_else(|| {
+        invalid_edit(format!(
+            "Ingredient '{}' not found in {}",
+            old_id, INGREDIENTS_FILE
+        ))
+    })?;
+    set_id(&definition, new_id);
+
+    let recipes_root = parse_cst(recipes_content, RECIPES_FILE)?;
+    let recipes = top_level_array(&recipes_root, "recipes", RECIPES_FILE)?;
+    for recipe in recipes.elements().into_iter().filter_map(|e| e.as_object()) {
+        if let Some(recipe_ingredients) = recipe.array_value("ingredients") {
+            for reference in matching_objects(&recipe_ingredients, "id", old_id) {
+                set_id(&reference, new_id);
+            }
+        }
+    }
+
+    Ok((recipes_root.to_string(), ingredients_root.to_string()))
+}
+
+pub(super) fn set_id(object: &CstObject, id: &str) {
+    if let Some(prop) = object.get("id") {
+        prop.set_value(string_value(id));
+    }
+}
+
+pub(super) fn parse_cst(content: &str, filename: &str) -> Result<CstRootNode, AppError> {
+    CstRootNode::parse(content, &ParseOptions::default()).map_err(|e| AppError::ParsingError {
+        message: format!("Invalid JSONC syntax in {}: {}", filename, e),
+    })
+}
+
+pub(super) fn top_level_array(
+    root: &CstRootNode,
+    key: &str,
+    filename: &str,
+) -> Result<CstArray, AppError> {
+    root.object_value()
+        .and_then(|object| object.array_value(key))
+        .ok_or_else(|| invalid_edit(format!("{} has no \"{}\" array", filename, key)))
+}
+
+fn recipe_ingredients_array(root: &CstRootNode, recipe: &str) -> Result<CstArray, AppError> {
+    let recipes = top_level_array(root, "recipes", RECIPES_FILE)?;
+    let object = find_object(&recipes, "name", recipe).ok_or_else(|| {
+        invalid_edit(format!(
+            "Recipe '{}' not found in {} (editing requires the exact name)",
+            recipe, RECIPES_FILE
+        ))
+    })?;
+    object
+        .array_value("ingredients")
+        .ok_or_else(|| invalid_edit(format!("Recipe '{}' has no ingredients array", recipe)))
+}
+
+fn find_object(array: &CstArray, key: &str, value: &str) -> Option<CstObject> {
+    matching_objects(array, key, value).into_iter().next()
+}
+
+/// Objects in the array whose string property `key` equals `value`
+pub(super) fn matching_objects(array: &CstArray, key: &str, value: &str) -> Vec<CstObject> {
+    array
+        .elements()
+        .into_iter()
+        .filter_map(|element| element.as_object())
+        .filter(|object| string_property(object, key).as_deref() == Some(value))
+        .collect()
+}
+
+fn string_property(object: &CstObject, key: &str) -> Option<String> {
+    object
+        .get(key)?
+        .value()?
+        .as_string_lit()?
+        .decoded_value()
+        .ok()
+}
+
+fn recipe_ingredient_value(id: &str, grams: f64) -> CstInputValue {
+    CstInputValue::Object(vec![
+        ("id".to_string(), string_value(id)),
+        ("grams".to_string(), number_value(grams)),
+    ])
+}
+
+fn string_value(value: &str) -> CstInputValue {
+    CstInputValue::String(value.to_string())
+}
+
+fn number_value(value: f64) -> CstInputValue {
+    CstInputValue::Number(value.to_string())
+}
+
+fn invalid_edit(message: String) -> AppError {
+    AppError::InvalidEdit { message }
+}
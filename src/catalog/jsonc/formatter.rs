@@ -0,0 +1,311 @@
+use crate::error::AppError;
+use jsonc_parser::ParseOptions;
+use jsonc_parser::cst::{
+    CstComment, CstContainerNode, CstLeafNode, CstNode, CstRootNode, ObjectPropName,
+};
+
+const INDENT: &str = "  ";
+
+/// Array whose elements get sorted by one of their string properties
+pub(super) struct SortRule {
+    pub array: &'static str,
+    pub key: &'static str,
+}
+
+/// Rewrite JSONC content in the canonical layout, keeping all comments
+///
+/// Every object and every array containing objects or comments is spread over
+/// multiple lines with two-space indentation. Arrays of plain values stay on one
+/// line. Single blank lines between entries are kept, trailing commas are dropped.
+pub(super) fn format_jsonc(
+    content: &str,
+    filename: &str,
+    sort: Option<SortRule>,
+    normalize_numbers: bool,
+) -> Result<String, AppError> {
+    let root = CstRootNode::parse(content, &ParseOptions::default()).map_err(|e| {
+        AppError::ParsingError {
+            message: format!("Invalid JSONC syntax in {}: {}", filename, e),
+        }
+    })?;
+
+    let mut document = collect(root.children(), normalize_numbers);
+    if let Some(rule) = sort {
+        sort_top_level_array(&mut document, &rule);
+    }
+
+    let mut output = String::new();
+    for entry in &document.entries {
+        write_trivia(&entry.leading, "", output.is_empty(), &mut output);
+        write_value(&entry.value, "", &mut output);
+        for comment in &entry.trailing {
+            output.push(' ');
+            output.push_str(comment);
+        }
+        output.push('\n');
+    }
+    write_trivia(&document.dangling, "", output.is_empty(), &mut output);
+    Ok(output)
+}
+
+enum Value {
+    Object(Container),
+    Array(Container),
+    Scalar(String),
+}
+
+#[derive(Default)]
+struct Container {
+    entries: Vec<Entry>,
+    /// Comments after the last entry
+    dangling: Vec<Trivia>,
+}
+
+struct Entry {
+    leading: Vec<Trivia>,
+    /// Property name as written (including quotes), absent for array elements
+    key: Option<String>,
+    value: Value,
+    /// Comments on the same line after the entry
+    trailing: Vec<String>,
+}
+
+enum Trivia {
+    BlankLine,
+    Comment(String),
+}
+
+/// Group the children of a container into entries with their surrounding comments
+fn collect(children: Vec<CstNode>, normalize_numbers: bool) -> Container {
+    let mut container = Container::default();
+    let mut pending = Vec::new();
+    let mut newlines = 0;
+    let mut seen_content = false;
+    let mut on_entry_line = false;
+
+    for child in children {
+        match child {
+            CstNode::Leaf(CstLeafNode::Newline(_)) => {
+                newlines += 1;
+                on_entry_line = false;
+            }
+            CstNode::Leaf(CstLeafNode::Whitespace(_) | CstLeafNode::Token(_)) => {}
+            CstNode::Leaf(CstLeafNode::Comment(comment)) => {
+                let text = comment_text(&comment);
+                match container.entries.last_mut() {
+                    Some(entry) if on_entry_line => entry.trailing.push(text),
+                    _ => {
+                        if newlines >= 2 && seen_content {
+                            pending.push(Trivia::BlankLine);
+                        }
+                        pending.push(Trivia::Comment(text));
+                    }
+                }
+                newlines = 0;
+                seen_content = true;
+            }
+            CstNode::Container(CstContainerNode::ObjectProp(prop)) => {
+                if newlines >= 2 && seen_content {
+                    pending.push(Trivia::BlankLine);
+                }
+                let key = prop.name().map(|name| match name {
+                    ObjectPropName::String(s) => s.to_string(),
+                    ObjectPropName::Word(w) => w.to_string(),
+                });
+                // Comments between a property's name and value move behind the value
+                let inner_comments = prop
+                    .children()
+                    .into_iter()
+                    .filter_map(|part| match part {
+                        CstNode::Leaf(CstLeafNode::Comment(comment)) => {
+                            Some(comment_text(&comment))
+                        }
+                        _ => None,
+                    })
+                    .collect();
+                let value = prop
+                    .value()
+                    .map(|node| convert(node, normalize_numbers))
+                    .unwrap_or_else(|| Value::Scalar("null".to_string()));
+                container.entries.push(Entry {
+                    leading: std::mem::take(&mut pending),
+                    key,
+                    value,
+                    trailing: inner_comments,
+                });
+                newlines = 0;
+                seen_content = true;
+                on_entry_line = true;
+            }
+            node => {
+                if newlines >= 2 && seen_content {
+                    pending.push(Trivia::BlankLine);
+                }
+                container.entries.push(Entry {
+                    leading: std::mem::take(&mut pending),
+                    key: None,
+                    value: convert(node, normalize_numbers),
+                    trailing: Vec::new(),
+                });
+                newlines = 0;
+                seen_content = true;
+                on_entry_line = true;
+            }
+        }
+    }
+
+    container.dangling = pending;
+    container
+}
+
+fn convert(node: CstNode, normalize_numbers: bool) -> Value {
+    match node {
+        CstNode::Container(CstContainerNode::Object(object)) => {
+            Value::Object(collect(object.children(), normalize_numbers))
+        }
+        CstNode::Container(CstContainerNode::Array(array)) => {
+            Value::Array(collect(array.children(), normalize_numbers))
+        }
+        CstNode::Leaf(CstLeafNode::NumberLit(number)) if normalize_numbers => {
+            Value::Scalar(normalize_number(&number.to_string()))
+        }
+        other => Value::Scalar(other.to_string()),
+    }
+}
+
+/// Shortest representation of a number, e.g. `100.0` becomes `100` and `5.50` becomes `5.5`
+fn normalize_number(raw: &str) -> String {
+    match raw.parse::<f64>() {
+        Ok(value) if value.is_finite() => value.to_string(),
+        _ => raw.to_string(),
+    }
+}
+
+fn comment_text(comment: &CstComment) -> String {
+    if comment.is_line_comment() {
+        comment.raw_value().trim_end().to_string()
+    } else {
+        comment.raw_value()
+    }
+}
+
+fn sort_top_level_array(document: &mut Container, rule: &SortRule) {
+    let Some(Value::Object(root)) = document.entries.first_mut().map(|e| &mut e.value) else {
+        return;
+    };
+    let Some(Value::Array(array)) = root
+        .entries
+        .iter_mut()
+        .find(|entry| entry.key.as_deref().map(unquote) == Some(rule.array))
+        .map(|entry| &mut entry.value)
+    else {
+        return;
+    };
+
+    array
+        .entries
+        .sort_by_cached_key(|entry| match &entry.value {
+            Value::Object(object) => object
+                .entries
+                .iter()
+                .find(|e| e.key.as_deref().map(unquote) == Some(rule.key))
+                .and_then(|e| match &e.value {
+                    Value::Scalar(s) => Some(unquote(s).to_lowercase()),
+                    _ => None,
+                })
+                .unwrap_or_default(),
+            _ => String::new(),
+        });
+}
+
+fn unquote(s: &str) -> &str {
+    s.trim_matches(|c| c == '"' || c == '\'')
+}
+
+fn write_value(value: &Value, indent: &str, output: &mut String) {
+    match value {
+        Value::Scalar(text) => output.push_str(text),
+        Value::Object(container) => write_container(container, '{', '}', indent, output),
+        Value::Array(container) if is_inline(container) => {
+            output.push('[');
+            for (i, entry) in container.entries.iter().enumerate() {
+                if i > 0 {
+                    output.push_str(", ");
+                }
+                write_value(&entry.value, indent, output);
+            }
+            output.push(']');
+        }
+        Value::Array(container) => write_container(container, '[', ']', indent, output),
+    }
+}
+
+/// Arrays of plain values without comments stay on one line
+fn is_inline(container: &Container) -> bool {
+    container.dangling.is_empty()
+        && container.entries.iter().all(|entry| {
+            entry.leading.is_empty()
+                && entry.trailing.is_empty()
+                && matches!(entry.value, Value::Scalar(_))
+        })
+}
+
+fn write_container(
+    container: &Container,
+    open: char,
+    close: char,
+    indent: &str,
+    output: &mut String,
+) {
+    output.push(open);
+    if container.entries.is_empty() && container.dangling.is_empty() {
+        output.push(close);
+        return;
+    }
+    output.push('\n');
+
+    let inner = format!("{}{}", indent, INDENT);
+    let last = container.entries.len().saturating_sub(1);
+    for (i, entry) in container.entries.iter().enumerate() {
+        write_trivia(&entry.leading, &inner, i == 0, output);
+        output.push_str(&inner);
+        if let Some(key) = &entry.key {
+            output.push_str(key);
+            output.push_str(": ");
+        }
+        write_value(&entry.value, &inner, output);
+        if i < last {
+            output.push(',');
+        }
+        for comment in &entry.trailing {
+            output.push(' ');
+            output.push_str(comment);
+        }
+        output.push('\n');
+    }
+    write_trivia(
+        &container.dangling,
+        &inner,
+        container.entries.is_empty(),
+        output,
+    );
+
+    output.push_str(indent);
+    output.push(close);
+}
+
+fn write_trivia(trivia: &[Trivia], indent: &str, at_start: bool, output: &mut String) {
+    let mut at_start = at_start;
+    for item in trivia {
+        match item {
+            Trivia::BlankLine if at_start => {}
+            Trivia::BlankLine => output.push('\n'),
+            Trivia::Comment(text) => {
+                output.push_str(indent);
+                output.push_str(text);
+                output.push('\n');
+            }
+        }
+        at_start = false;
+    }
+}
@@ -4,8 +4,14 @@ use std::path::Path;
 
 const RECIPE_SCHEMA: &str = include_str!("recipes.schema.json");
 const INGREDIENT_SCHEMA: &str = include_str!("ingredients.schema.json");
+const PLAN_SCHEMA: &str = include_str!("plans.schema.json");
+const DIARY_SCHEMA: &str = include_str!("diary.schema.json");
+const PANTRY_SCHEMA: &str = include_str!("pantry.schema.json");
+const TARGETS_SCHEMA: &str = include_str!("targets.schema.json");
 const RECIPE_TEMPLATE: &str = include_str!("recipes.template.jsonc");
 const INGREDIENT_TEMPLATE: &str = include_str!("ingredients.template.jsonc");
+const PLAN_TEMPLATE: &str = include_str!("plans.template.jsonc");
+const TARGETS_TEMPLATE: &str = include_str!("targets.template.jsonc");
 
 /// Initialize a complete catalog with all required files and editor support
 pub fn initialize(output_dir: &Path) -> AppResult<()> {
@@ -27,6 +33,30 @@ pub(super) fn create_ingredient_schema() -> Result<Value, crate::error::AppError
     })
 }
 
+pub(super) fn create_plan_schema() -> Result<Value, crate::error::AppError> {
+    serde_json::from_str(PLAN_SCHEMA).map_err(|e| crate::error::AppError::InvalidSchema {
+        message: format!("Failed to parse embedded plan schema: {}", e),
+    })
+}
+
+pub(super) fn create_diary_schema() -> Result<Value, crate::error::AppError> {
+    serde_json::from_str(DIARY_SCHEMA).map_err(|e| crate::error::AppError::InvalidSchema {
+        message: format!("Failed to parse embedded diary schema: {}", e),
+    })
+}
+
+pub(super) fn create_pantry_schema() -> Result<Value, crate::error::AppError> {
+    serde_json::from_str(PANTRY_SCHEMA).map_err(|e| crate::error::AppError::InvalidSchema {
+        message: format!("Failed to parse embedded pantry schema: {}", e),
+    })
+}
+
+pub(super) fn create_targets_schema() -> Result<Value, crate::error::AppError> {
+    serde_json::from_str(TARGETS_SCHEMA).map_err(|e| crate::error::AppError::InvalidSchema {
+        message: format!("Failed to parse embedded targets schema: {}", e),
+    })
+}
+
 /// Create the required data files with starter content
 fn create_data_files(output_dir: &Path) -> AppResult<()> {
     let recipes_path = output_dir.join("recipes.jsonc");
@@ -35,6 +65,12 @@ fn create_data_files(output_dir: &Path) -> AppResult<()> {
     let ingredients_path = output_dir.join("ingredients.jsonc");
     std::fs::write(ingredients_path, INGREDIENT_TEMPLATE)?;
 
+    let plans_path = output_dir.join("plans.jsonc");
+    std::fs::write(plans_path, PLAN_TEMPLATE)?;
+
+    let targets_path = output_dir.join("targets.jsonc");
+    std::fs::write(targets_path, TARGETS_TEMPLATE)?;
+
     Ok(())
 }
 
@@ -46,5 +82,19 @@ fn create_schemas(output_dir: &Path) -> AppResult<()> {
     let ingredient_schema_path = output_dir.join("ingredients.schema.json");
     std::fs::write(&ingredient_schema_path, INGREDIENT_SCHEMA)?;
 
+    let plan_schema_path = output_dir.join("plans.schema.json");
+    std::fs::write(&plan_schema_path, PLAN_SCHEMA)?;
+
+    let targets_schema_path = output_dir.join("targets.schema.json");
+    std::fs::write(&targets_schema_path, TARGETS_SCHEMA)?;
+
+    // The diary itself is created by the first 'nutriterm log'
+    let diary_schema_path = output_dir.join("diary.schema.json");
+    std::fs::write(&diary_schema_path, DIARY_SCHEMA)?;
+
+    // Likewise the pantry, by the first 'nutriterm pantry add'
+    let pantry_schema_path = output_dir.join("pantry.schema.json");
+    std::fs::write(&pantry_schema_path, PANTRY_SCHEMA)?;
+
     Ok(())
 }
@@ -1,12 +1,19 @@
-use super::initialization::{create_ingredient_schema, create_recipe_schema};
-use crate::catalog::items::{Ingredient, Recipe, WeightedIngredient};
+use super::initialization::{create_ingredient_schema, create_plan_schema, create_recipe_schema};
+use crate::catalog::Catalog;
+use crate::catalog::items::{
+    DayPlan, Ingredient, PlannedMeal, Portion, Recipe, WeightedIngredient,
+};
 use crate::error::{AppError, DuplicateGroup};
 use crate::utils::suggestions::find_best_suggestion;
 use jsonschema::Validator;
 use serde::{Deserialize, de::DeserializeOwned};
 use serde_json::Value;
 use std::collections::HashMap;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+
+pub(super) const RECIPES_FILE: &str = "recipes.jsonc";
+pub(super) const INGREDIENTS_FILE: &str = "ingredients.jsonc";
+pub(super) const PLANS_FILE: &str = "plans.jsonc";
 
 #[derive(Deserialize)]
 struct JsonRecipes {
@@ -16,7 +23,10 @@ struct JsonRecipes {
 #[derive(Deserialize)]
 struct JsonRecipe {
     name: String,
+    #[serde(default)]
+    tags: Vec<String>,
     ingredients: Vec<JsonRecipeIngredient>,
+    cooked_grams: Option<f64>,
 }
 
 #[derive(Deserialize)]
@@ -38,32 +48,82 @@ struct JsonIngredient {
     protein_per_100g: f64,
     fat_per_100g: f64,
     fiber_per_100g: f64,
+    aisle: Option<String>,
+    package_grams: Option<f64>,
 }
 
-pub fn load_recipes(data_dir: &Path) -> Result<Vec<Recipe>, AppError> {
-    let json_recipes: JsonRecipes =
-        load_jsonc_file(data_dir, "recipes.jsonc", create_recipe_schema)?;
+#[derive(Deserialize)]
+struct JsonPlans {
+    days: Vec<JsonDay>,
+}
+
+#[derive(Deserialize)]
+struct JsonDay {
+    name: String,
+    meals: Vec<JsonMeal>,
+}
+
+#[derive(Deserialize)]
+struct JsonMeal {
+    meal: String,
+    recipe: String,
+    portions: Option<f64>,
+    grams: Option<f64>,
+}
 
+/// The files `load_catalog` reads
+pub fn catalog_files(data_dir: &Path) -> Vec<PathBuf> {
+    vec![data_dir.join(RECIPES_FILE), data_dir.join(INGREDIENTS_FILE)]
+}
+
+pub fn load_catalog(data_dir: &Path) -> Result<Catalog, AppError> {
+    let json_recipes: JsonRecipes = load_jsonc_file(data_dir, RECIPES_FILE, create_recipe_schema)?;
     check_recipe_uniqueness(&json_recipes.recipes)?;
 
     let json_ingredients = load_json_ingredients(data_dir)?;
+    build_catalog(json_recipes, json_ingredients)
+}
+
+/// Load a catalog from file contents that have not been written to disk yet
+pub(super) fn load_catalog_from_str(
+    recipes_content: &str,
+    ingredients_content: &str,
+) -> Result<Catalog, AppError> {
+    let json_recipes: JsonRecipes =
+        parse_jsonc_content(recipes_content, RECIPES_FILE, create_recipe_schema)?;
+    check_recipe_uniqueness(&json_recipes.recipes)?;
+
+    let json_ingredients: JsonIngredients = parse_jsonc_content(
+        ingredients_content,
+        INGREDIENTS_FILE,
+        create_ingredient_schema,
+    )?;
+    build_catalog(json_recipes, json_ingredients)
+}
+
+fn build_catalog(
+    json_recipes: JsonRecipes,
+    json_ingredients: JsonIngredients,
+) -> Result<Catalog, AppError> {
     check_ingredient_uniqueness(&json_ingredients.ingredients)?;
-    let ingredient_map: HashMap<String, Ingredient> = json_ingredients
+    let ingredients: Vec<Ingredient> = json_ingredients
         .ingredients
         .into_iter()
-        .map(|json_ing| {
-            (
-                json_ing.id,
-                Ingredient {
-                    name: json_ing.name,
-                    carbs_per_100g: json_ing.carbs_per_100g,
-                    protein_per_100g: json_ing.protein_per_100g,
-                    fat_per_100g: json_ing.fat_per_100g,
-                    fiber_per_100g: json_ing.fiber_per_100g,
-                },
-            )
+        .map(|json_ing| Ingredient {
+            id: json_ing.id,
+            name: json_ing.name,
+            carbs_per_100g: json_ing.carbs_per_100g,
+            protein_per_100g: json_ing.protein_per_100g,
+            fat_per_100g: json_ing.fat_per_100g,
+            fiber_per_100g: json_ing.fiber_per_100g,
+            aisle: json_ing.aisle,
+            package_grams: json_ing.package_grams,
         })
         .collect();
+    let ingredient_map: HashMap<&str, &Ingredient> = ingredients
+        .iter()
+        .map(|ingredient| (ingredient.id.as_str(), ingredient))
+        .collect();
 
     let mut recipes = Vec::new();
 
@@ -71,36 +131,92 @@ pub fn load_recipes(data_dir: &Path) -> Result<Vec<Recipe>, AppError> {
         let mut recipe_ingredients = Vec::new();
 
         for json_ingredient in json_recipe.ingredients {
-            let ingredient = ingredient_map.get(&json_ingredient.id).ok_or_else(|| {
-                let available_ids: Vec<String> = ingredient_map.keys().cloned().collect();
-                let suggestion = find_best_suggestion(&json_ingredient.id, &available_ids);
-
-                AppError::UnknownIngredient {
-                    recipe: json_recipe.name.clone(),
-                    ingredient: json_ingredient.id.clone(),
-                    suggestion,
-                    available_ids,
-                }
-            })?;
+            let ingredient = ingredient_map
+                .get(json_ingredient.id.as_str())
+                .ok_or_else(|| {
+                    let available_ids: Vec<String> =
+                        ingredients.iter().map(|i| i.id.clone()).collect();
+                    let suggestion = find_best_suggestion(&json_ingredient.id, &available_ids);
+
+                    AppError::UnknownIngredient {
+                        recipe: json_recipe.name.clone(),
+                        ingredient: json_ingredient.id.clone(),
+                        suggestion,
+                        available_ids,
+                    }
+                })?;
 
             recipe_ingredients.push(WeightedIngredient {
-                ingredient: ingredient.clone(),
+                ingredient: (*ingredient).clone(),
                 grams: json_ingredient.grams,
             });
         }
 
         recipes.push(Recipe {
             name: json_recipe.name,
+            tags: json_recipe.tags,
             ingredients: recipe_ingredients,
+            cooked_grams: json_recipe.cooked_grams,
         });
     }
 
-    Ok(recipes)
+    Ok(Catalog {
+        ingredients,
+        recipes,
+    })
+}
+
+/// Load the meal plans, resolving recipe references against the catalog
+pub fn load_plans(data_dir: &Path, catalog: &Catalog) -> Result<Vec<DayPlan>, AppError> {
+    let plans_path = data_dir.join(PLANS_FILE);
+    if !plans_path.exists() {
+        return Err(AppError::PlansNotFound { path: plans_path });
+    }
+    let json_plans: JsonPlans = load_jsonc_file(data_dir, PLANS_FILE, create_plan_schema)?;
+    check_uniqueness(&json_plans.days, PLANS_FILE, "day name", |day| {
+        (&day.name, format!("day '{}'", day.name))
+    })?;
+
+    let recipe_map: HashMap<&str, &Recipe> = catalog
+        .recipes
+        .iter()
+        .map(|recipe| (recipe.name.as_str(), recipe))
+        .collect();
+
+    let mut days = Vec::new();
+    for json_day in json_plans.days {
+        let mut meals = Vec::new();
+        for json_meal in json_day.meals {
+            let recipe = recipe_map.get(json_meal.recipe.as_str()).ok_or_else(|| {
+                let available: Vec<String> =
+                    catalog.recipes.iter().map(|r| r.name.clone()).collect();
+                AppError::UnknownRecipe {
+                    day: json_day.name.clone(),
+                    recipe: json_meal.recipe.clone(),
+                    suggestion: find_best_suggestion(&json_meal.recipe, &available),
+                }
+            })?;
+            let portion = match json_meal.grams {
+                Some(grams) => Portion::Grams(grams),
+                None => Portion::Multiplier(json_meal.portions.unwrap_or(1.0)),
+            };
+            meals.push(PlannedMeal {
+                meal: json_meal.meal,
+                recipe: (*recipe).clone(),
+                portion,
+            });
+        }
+        days.push(DayPlan {
+            name: json_day.name,
+            meals,
+        });
+    }
+    Ok(days)
 }
 
 fn load_json_ingredients(data_dir: &Path) -> Result<JsonIngredients, AppError> {
     let ingredients: JsonIngredients =
-        load_jsonc_file(data_dir, "ingredients.jsonc", create_ingredient_schema)?;
+        load_jsonc_file(data_dir, INGREDIENTS_FILE, create_ingredient_schema)?;
     // Note: duplicates will be handled at domain level, not here
     Ok(ingredients)
 }
@@ -110,13 +226,24 @@ fn load_jsonc_file<T: DeserializeOwned>(
     filename: &str,
     schema_generator: fn() -> Result<Value, AppError>,
 ) -> Result<T, AppError> {
+    let content = read_catalog_file(data_dir, filename)?;
+    parse_jsonc_content(&content, filename, schema_generator)
+}
+
+pub(super) fn read_catalog_file(data_dir: &Path, filename: &str) -> Result<String, AppError> {
     let file_path = data_dir.join(filename);
-    let content = std::fs::read_to_string(&file_path).map_err(|e| AppError::FileUnreadable {
-        path: file_path.clone(),
+    std::fs::read_to_string(&file_path).map_err(|e| AppError::FileUnreadable {
+        path: file_path,
         io_error: e.to_string(),
-    })?;
+    })
+}
 
-    let json_value = jsonc_parser::parse_to_serde_value(&content, &Default::default())
+pub(super) fn parse_jsonc_content<T: DeserializeOwned>(
+    content: &str,
+    filename: &str,
+    schema_generator: fn() -> Result<Value, AppError>,
+) -> Result<T, AppError> {
+    let json_value = jsonc_parser::parse_to_serde_value(content, &Default::default())
         .map_err(|e| AppError::ParsingError {
             message: format!(
                 "Invalid JSONC syntax in {}: {}\n\nTip: Check for missing commas, brackets, or quotes. Most editors highlight syntax errors when you save the file with a .jsonc extension.",
@@ -170,7 +297,7 @@ fn check_with_schema(
 }
 
 fn check_recipe_uniqueness(recipes: &[JsonRecipe]) -> Result<(), AppError> {
-    check_uniqueness(recipes, "recipes.jsonc", "recipe name", |recipe| {
+    check_uniqueness(recipes, RECIPES_FILE, "recipe name", |recipe| {
         (&recipe.name, format!("recipe '{}'", recipe.name))
     })
 }
@@ -178,7 +305,7 @@ fn check_recipe_uniqueness(recipes: &[JsonRecipe]) -> Result<(), AppError> {
 fn check_ingredient_uniqueness(ingredients: &[JsonIngredient]) -> Result<(), AppError> {
     check_uniqueness(
         ingredients,
-        "ingredients.jsonc",
+        INGREDIENTS_FILE,
         "ingredient ID",
         |ingredient| (&ingredient.id, ingredient.name.clone()),
     )
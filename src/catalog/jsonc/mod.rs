@@ -1,6 +1,15 @@
+mod diary;
+mod editor;
+mod formatter;
 mod initialization;
 mod loader;
+mod pantry;
+mod targets;
 
 // JSONC file format implementation
+pub use diary::{append_diary_entry, load_diary};
+pub use editor::plan_edit;
 pub use initialization::initialize;
-pub use loader::load_recipes;
+pub use loader::{catalog_files, load_catalog, load_plans};
+pub use pantry::{load_pantry, update_pantry};
+pub use targets::{load_targets, targets_file};
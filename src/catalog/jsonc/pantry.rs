@@ -0,0 +1,141 @@
+use super::diary::rounded_number;
+use super::editor::{matching_objects, parse_cst, set_id, top_level_array};
+use super::initialization::create_pantry_schema;
+use super::loader::{parse_jsonc_content, read_catalog_file};
+use crate::catalog::items::{Pantry, PantryItem};
+use crate::catalog::{Catalog, StockChange};
+use crate::error::AppError;
+use crate::utils::suggestions::find_best_suggestion;
+use jsonc_parser::cst::CstInputValue;
+use serde::Deserialize;
+use std::path::Path;
+
+pub(super) const PANTRY_FILE: &str = "pantry.jsonc";
+const PANTRY_TEMPLATE: &str = include_str!("pantry.template.jsonc");
+
+#[derive(Deserialize)]
+struct JsonPantry {
+    stock: Vec<JsonPantryItem>,
+}
+
+#[derive(Deserialize)]
+struct JsonPantryItem {
+    id: String,
+    grams: f64,
+    low_grams: Option<f64>,
+}
+
+/// Load the pantry, checking its ingredient IDs against the catalog; a missing pantry is empty
+pub fn load_pantry(data_dir: &Path, catalog: &Catalog) -> Result<Pantry, AppError> {
+    if !data_dir.join(PANTRY_FILE).exists() {
+        return Ok(Pantry::default());
+    }
+    let content = read_catalog_file(data_dir, PANTRY_FILE)?;
+    check_pantry(&content, catalog)
+}
+
+/// Parse pantry content, checking its ingredient IDs against the catalog
+pub(super) fn check_pantry(content: &str, catalog: &Catalog) -> Result<Pantry, AppError> {
+    let pantry = parse_pantry(content)?;
+
+    let known_ids: Vec<String> = catalog.ingredients.iter().map(|i| i.id.clone()).collect();
+    if let Some(unknown) = pantry.items.iter().find(|i| !known_ids.contains(&i.id)) {
+        return Err(AppError::UnknownPantryIngredient {
+            ingredient: unknown.id.clone(),
+            suggestion: find_best_suggestion(&unknown.id, &known_ids),
+        });
+    }
+    Ok(pantry)
+}
+
+/// Apply stock changes, creating the pantry if needed and keeping its comments
+///
+/// Stock never drops below zero. Repeated entries for an ingredient are
+/// collapsed into the first one.
+pub fn update_pantry(data_dir: &Path, changes: &[StockChange]) -> Result<(), AppError> {
+    let path = data_dir.join(PANTRY_FILE);
+    let content = if path.exists() {
+        read_catalog_file(data_dir, PANTRY_FILE)?
+    } else {
+        PANTRY_TEMPLATE.to_string()
+    };
+    let mut pantry = parse_pantry(&content)?;
+
+    let root = parse_cst(&content, PANTRY_FILE)?;
+    let stock = top_level_array(&root, "stock", PANTRY_FILE)?;
+    for change in changes {
+        let grams = (pantry.stock(&change.id) + change.grams).max(0.0);
+        let mut existing = matching_objects(&stock, "id", &change.id);
+        if existing.is_empty() {
+            let mut properties = vec![
+                ("id".to_string(), CstInputValue::String(change.id.clone())),
+                ("grams".to_string(), rounded_number(grams)),
+            ];
+            if let Some(low) = change.low_grams {
+                properties.push(("low_grams".to_string(), rounded_number(low)));
+            }
+            stock.append(CstInputValue::Object(properties));
+        } else {
+            let first = existing.remove(0);
+            for (key, value) in [("grams", Some(grams)), ("low_grams", change.low_grams)] {
+                let Some(value) = value else { continue };
+                match first.get(key) {
+                    Some(prop) => prop.set_value(rounded_number(value)),
+                    None => {
+                        first.append(key, rounded_number(value));
+                    }
+                }
+            }
+            for duplicate in existing {
+                duplicate.remove();
+            }
+        }
+
+        // Later changes to the same ingredient build on this one
+        pantry.items.retain(|item| item.id != change.id);
+        pantry.items.push(PantryItem {
+            id: change.id.clone(),
+            grams,
+            low_grams: None,
+        });
+    }
+
+    let updated = root.to_string();
+    parse_pantry(&updated)?;
+    std::fs::write(path, updated)?;
+    Ok(())
+}
+
+/// Point every stock entry for `old_id` at `new_id`, keeping comments
+pub(super) fn rename_pantry_ingredient(
+    content: &str,
+    old_id: &str,
+    new_id: &str,
+) -> Result<String, AppError> {
+    let root = parse_cst(content, PANTRY_FILE)?;
+    let stock = top_level_array(&root, "stock", PANTRY_FILE)?;
+    for item in matching_objects(&stock, "id", old_id) {
+        set_id(&item, new_id);
+    }
+    Ok(root.to_string())
+}
+
+/// Parse and validate pantry content, adding up repeated entries for an ingredient
+fn parse_pantry(content: &str) -> Result<Pantry, AppError> {
+    let json: JsonPantry = parse_jsonc_content(content, PANTRY_FILE, create_pantry_schema)?;
+    let mut items: Vec<PantryItem> = Vec::new();
+    for json_item in json.stock {
+        match items.iter_mut().find(|item| item.id == json_item.id) {
+            Some(existing) => {
+                existing.grams += json_item.grams;
+                existing.low_grams = existing.low_grams.or(json_item.low_grams);
+            }
+            None => items.push(PantryItem {
+                id: json_item.id,
+                grams: json_item.grams,
+                low_grams: json_item.low_grams,
+            }),
+        }
+    }
+    Ok(Pantry { items })
+}
@@ -0,0 +1,101 @@
+use super::initialization::create_targets_schema;
+use super::loader::{parse_jsonc_content, read_catalog_file};
+use crate::catalog::items::{DailyTargets, Nutrient, NutrientTarget};
+use crate::error::AppError;
+use crate::utils::suggestions::find_best_suggestion;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+const TARGETS_FILE: &str = "targets.jsonc";
+
+#[derive(Deserialize)]
+struct JsonTargetsFile {
+    #[serde(default)]
+    daily: JsonTargets,
+    #[serde(default)]
+    people: BTreeMap<String, JsonTargets>,
+}
+
+#[derive(Deserialize, Default)]
+struct JsonTargets {
+    carbs: Option<JsonTarget>,
+    protein: Option<JsonTarget>,
+    fat: Option<JsonTarget>,
+    fiber: Option<JsonTarget>,
+    calories: Option<JsonTarget>,
+}
+
+#[derive(Deserialize, Clone, Copy)]
+struct JsonTarget {
+    min: Option<f64>,
+    max: Option<f64>,
+}
+
+impl JsonTargets {
+    fn get(&self, nutrient: Nutrient) -> Option<JsonTarget> {
+        match nutrient {
+            Nutrient::Carbs => self.carbs,
+            Nutrient::Protein => self.protein,
+            Nutrient::Fat => self.fat,
+            Nutrient::Fiber => self.fiber,
+            Nutrient::Calories => self.calories,
+        }
+    }
+}
+
+/// The file `load_targets` reads
+pub fn targets_file(data_dir: &Path) -> PathBuf {
+    data_dir.join(TARGETS_FILE)
+}
+
+/// Load the daily targets, with a person's targets overriding the catalog's nutrient by nutrient
+///
+/// Without a targets file there are no targets, unless a person was asked for.
+pub fn load_targets(
+    data_dir: &Path,
+    person: Option<&str>,
+) -> Result<Option<DailyTargets>, AppError> {
+    if !data_dir.join(TARGETS_FILE).exists() {
+        return match person {
+            Some(person) => Err(AppError::UnknownPerson {
+                person: person.to_string(),
+                suggestion: None,
+                available: Vec::new(),
+            }),
+            None => Ok(None),
+        };
+    }
+
+    let content = read_catalog_file(data_dir, TARGETS_FILE)?;
+    let json: JsonTargetsFile = parse_jsonc_content(&content, TARGETS_FILE, create_targets_schema)?;
+
+    let personal = match person {
+        Some(person) => Some(json.people.get(person).ok_or_else(|| {
+            let available: Vec<String> = json.people.keys().cloned().collect();
+            AppError::UnknownPerson {
+                person: person.to_string(),
+                suggestion: find_best_suggestion(person, &available),
+                available,
+            }
+        })?),
+        None => None,
+    };
+
+    let targets = Nutrient::ALL
+        .into_iter()
+        .filter_map(|nutrient| {
+            let target = personal
+                .and_then(|p| p.get(nutrient))
+                .or_else(|| json.daily.get(nutrient))?;
+            Some((
+                nutrient,
+                NutrientTarget {
+                    min: target.min,
+                    max: target.max,
+                },
+            ))
+        })
+        .collect();
+    Ok(Some(DailyTargets { targets }))
+}
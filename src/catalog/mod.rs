@@ -1,9 +1,18 @@
+//! Loading and editing the catalog: the directory holding recipes.jsonc, ingredients.jsonc
+//! and the optional plans, targets, diary and pantry files
+//!
+//! Functions without a directory argument find the catalog like git finds a repository,
+//! starting at the current directory and walking up through its parents.
+
 mod discovery;
 pub mod items;
 mod jsonc;
 
 use crate::error::AppResult;
-use std::path::Path;
+use crate::search;
+use std::path::{Path, PathBuf};
+
+pub use discovery::find_dir;
 
 // Domain layer functions (orchestrate discovery and JSONC implementation)
 
@@ -12,8 +21,181 @@ pub fn initialize(path: &Path) -> AppResult<()> {
     jsonc::initialize(path)
 }
 
+/// All ingredients and recipes defined in a catalog, in file order
+#[derive(Debug, Clone)]
+pub struct Catalog {
+    /// Ingredients from ingredients.jsonc
+    pub ingredients: Vec<items::Ingredient>,
+    /// Recipes from recipes.jsonc, with their ingredients resolved
+    pub recipes: Vec<items::Recipe>,
+}
+
+impl Catalog {
+    /// The recipe with this exact name, otherwise the only one matching the search terms
+    pub fn recipe(&self, query: &str) -> AppResult<&items::Recipe> {
+        search::resolve_recipe(&self.recipes, query)
+    }
+
+    /// The ingredient with this exact ID or name, otherwise the only one matching the search terms
+    pub fn ingredient(&self, query: &str) -> AppResult<&items::Ingredient> {
+        search::ingredients::resolve_ingredient(&self.ingredients, query)
+    }
+}
+
+/// Load the complete catalog (ingredients and recipes)
+pub fn load() -> AppResult<Catalog> {
+    let catalog_dir = discovery::find_dir()?;
+    jsonc::load_catalog(&catalog_dir)
+}
+
+/// Load the complete catalog from a known directory
+pub fn load_from(catalog_dir: &Path) -> AppResult<Catalog> {
+    jsonc::load_catalog(catalog_dir)
+}
+
+/// The files the catalog is loaded from, for noticing when they change
+pub fn source_files() -> AppResult<Vec<PathBuf>> {
+    let catalog_dir = discovery::find_dir()?;
+    Ok(jsonc::catalog_files(&catalog_dir))
+}
+
+/// The daily targets file, for noticing when it changes; it need not exist
+pub fn targets_file() -> AppResult<PathBuf> {
+    let catalog_dir = discovery::find_dir()?;
+    Ok(jsonc::targets_file(&catalog_dir))
+}
+
 /// Load recipes from catalog
 pub fn load_recipes() -> AppResult<Vec<items::Recipe>> {
+    Ok(load()?.recipes)
+}
+
+/// Load the meal plans from plans.jsonc, with recipes taken from the catalog
+pub fn load_plans() -> AppResult<Vec<items::DayPlan>> {
+    let catalog_dir = discovery::find_dir()?;
+    let catalog = jsonc::load_catalog(&catalog_dir)?;
+    jsonc::load_plans(&catalog_dir, &catalog)
+}
+
+/// Load the daily targets from targets.jsonc, for a person if given
+///
+/// Returns `None` when the catalog has no targets file.
+pub fn load_targets(person: Option<&str>) -> AppResult<Option<items::DailyTargets>> {
+    let catalog_dir = discovery::find_dir()?;
+    jsonc::load_targets(&catalog_dir, person)
+}
+
+/// Load the food diary (empty if nothing has been logged yet)
+pub fn load_diary() -> AppResult<Vec<items::DiaryEntry>> {
+    let catalog_dir = discovery::find_dir()?;
+    jsonc::load_diary(&catalog_dir)
+}
+
+/// Append an entry to the food diary
+pub fn append_diary_entry(entry: &items::DiaryEntry) -> AppResult<()> {
+    let catalog_dir = discovery::find_dir()?;
+    jsonc::append_diary_entry(&catalog_dir, entry)
+}
+
+/// Load the pantry stock (empty if nothing has been stocked yet)
+pub fn load_pantry() -> AppResult<items::Pantry> {
+    let catalog_dir = discovery::find_dir()?;
+    let catalog = jsonc::load_catalog(&catalog_dir)?;
+    jsonc::load_pantry(&catalog_dir, &catalog)
+}
+
+/// A change to one ingredient's pantry stock
+#[derive(Debug, Clone)]
+pub struct StockChange {
+    /// Ingredient ID
+    pub id: String,
+    /// Grams added, or removed when negative
+    pub grams: f64,
+    /// New low-stock mark, if it should change
+    pub low_grams: Option<f64>,
+}
+
+/// Apply stock changes to the pantry, keeping its comments
+pub fn update_pantry(changes: &[StockChange]) -> AppResult<()> {
+    let catalog_dir = discovery::find_dir()?;
+    jsonc::update_pantry(&catalog_dir, changes)
+}
+
+/// A change to the catalog's data files
+#[derive(Debug, Clone)]
+pub enum Edit {
+    /// Append an ingredient to ingredients.jsonc
+    AddIngredient(items::Ingredient),
+    /// Append a recipe to recipes.jsonc
+    AddRecipe {
+        /// Recipe name
+        name: String,
+        /// Tags, possibly none
+        tags: Vec<String>,
+        /// Ingredient IDs with their amounts in grams
+        ingredients: Vec<(String, f64)>,
+    },
+    /// Set an ingredient's amount in a recipe, adding the ingredient if missing
+    SetRecipeIngredient {
+        /// Recipe name
+        recipe: String,
+        /// Ingredient ID
+        ingredient_id: String,
+        /// New amount
+        grams: f64,
+    },
+    /// Remove an ingredient from a recipe
+    RemoveRecipeIngredient {
+        /// Recipe name
+        recipe: String,
+        /// Ingredient ID
+        ingredient_id: String,
+    },
+    /// Change an ingredient's ID, updating every recipe and pantry entry that references it
+    RenameIngredient {
+        /// Current ID
+        old_id: String,
+        /// ID to change it to
+        new_id: String,
+    },
+    /// Rewrite both files in the canonical layout
+    Format(FormatOptions),
+}
+
+/// Optional rules applied on top of the canonical layout
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FormatOptions {
+    /// Order ingredients by ID
+    pub sort_ingredients: bool,
+    /// Order recipes by name
+    pub sort_recipes: bool,
+    /// Write numbers in their shortest form (e.g. `100.0` becomes `100`)
+    pub normalize_numbers: bool,
+}
+
+/// New content for a catalog file, produced by an edit
+#[derive(Debug, Clone)]
+pub struct FileChange {
+    /// The file to write
+    pub path: PathBuf,
+    /// Its content before the edit
+    pub original: String,
+    /// Its content after the edit
+    pub updated: String,
+}
+
+/// Compute and validate the file changes for an edit, without writing them
+///
+/// Comments, formatting and ordering of the files are preserved.
+pub fn plan_edit(edit: &Edit) -> AppResult<Vec<FileChange>> {
     let catalog_dir = discovery::find_dir()?;
-    jsonc::load_recipes(&catalog_dir)
+    jsonc::plan_edit(&catalog_dir, edit)
+}
+
+/// Write previously planned file changes to disk
+pub fn write_changes(changes: &[FileChange]) -> AppResult<()> {
+    for change in changes {
+        std::fs::write(&change.path, &change.updated)?;
+    }
+    Ok(())
 }
@@ -0,0 +1,164 @@
+use super::display::{
+    TableOptions, apply_standard_style, format_calories, format_difference,
+    format_number_with_unit, render_nutrition_table,
+};
+use crate::catalog;
+use crate::catalog::items::{Nutrition, Recipe};
+use crate::error::AppResult;
+use crate::search::resolve_recipe;
+use std::io::{self, Write};
+use tabled::builder::Builder;
+
+/// A row of the comparison table
+struct Metric {
+    label: &'static str,
+    value: fn(&Nutrition) -> f64,
+    is_energy: bool,
+}
+
+const METRICS: [Metric; 6] = [
+    Metric {
+        label: "Weight",
+        value: |n| n.grams,
+        is_energy: false,
+    },
+    Metric {
+        label: "Net carbs",
+        value: |n| n.carbs,
+        is_energy: false,
+    },
+    Metric {
+        label: "Protein",
+        value: |n| n.protein,
+        is_energy: false,
+    },
+    Metric {
+        label: "Fat",
+        value: |n| n.fat,
+        is_energy: false,
+    },
+    Metric {
+        label: "Fiber",
+        value: |n| n.fiber,
+        is_energy: false,
+    },
+    Metric {
+        label: "Calories",
+        value: |n| n.calories,
+        is_energy: true,
+    },
+];
+
+pub fn run(queries: &[String]) -> AppResult<()> {
+    compare(&catalog::load_recipes()?, queries)
+}
+
+/// Compare the recipes the queries resolve to, the first being the baseline
+pub(crate) fn compare(recipes: &[Recipe], queries: &[String]) -> AppResult<()> {
+    let selected = queries
+        .iter()
+        .map(|query| resolve_recipe(recipes, query))
+        .collect::<AppResult<Vec<_>>>()?;
+
+    let mut stdout = io::stdout();
+    let mut any_unique = false;
+    for (index, recipe) in selected.iter().enumerate() {
+        let unique = unique_ingredient_ids(index, &selected);
+        any_unique |= !unique.is_empty();
+
+        writeln!(stdout, "Recipe: {}", recipe.name)?;
+        writeln!(stdout)?;
+        render_nutrition_table(
+            &recipe.ingredients,
+            &TableOptions {
+                highlight: &unique,
+                ..Default::default()
+            },
+            &mut stdout,
+        )?;
+        writeln!(stdout)?;
+    }
+    if any_unique {
+        writeln!(stdout, "* Not used in the other compared recipes")?;
+        writeln!(stdout)?;
+    }
+
+    writeln!(
+        stdout,
+        "Comparison (differences relative to {}):",
+        selected[0].name
+    )?;
+    writeln!(stdout)?;
+    render_comparison(&selected, &mut stdout)?;
+    Ok(())
+}
+
+/// IDs of ingredients in `all[index]` that none of the other recipes use
+///
+/// Goes by position so that a recipe compared with itself counts as another recipe.
+fn unique_ingredient_ids<'a>(index: usize, all: &[&'a Recipe]) -> Vec<&'a str> {
+    all[index]
+        .ingredients
+        .iter()
+        .map(|i| i.ingredient.id.as_str())
+        .filter(|id| {
+            all.iter()
+                .enumerate()
+                .filter(|(other_index, _)| *other_index != index)
+                .all(|(_, other)| !other.uses_ingredient(id))
+        })
+        .collect()
+}
+
+fn render_comparison<W: Write>(recipes: &[&Recipe], writer: &mut W) -> io::Result<()> {
+    let mut builder = Builder::default();
+
+    let mut header = vec![String::new(), recipes[0].name.clone()];
+    for recipe in &recipes[1..] {
+        header.push(recipe.name.clone());
+        header.push("Difference".to_string());
+    }
+    builder.push_record(header);
+
+    let totals: Vec<Nutrition> = recipes.iter().map(|r| r.totals()).collect();
+    for metric in &METRICS {
+        builder.push_record(comparison_row(metric.label, &totals, metric));
+    }
+
+    let per_100g: Vec<Nutrition> = recipes
+        .iter()
+        .map(|r| r.per_100g().unwrap_or_default())
+        .collect();
+    // Weight per 100 g is always 100 g, so it is left out
+    for metric in &METRICS[1..] {
+        builder.push_record(comparison_row(
+            &format!("{} / 100 g", metric.label),
+            &per_100g,
+            metric,
+        ));
+    }
+
+    let mut table = builder.build();
+    apply_standard_style(&mut table);
+    writeln!(writer, "{}", table)
+}
+
+fn comparison_row(label: &str, nutrition: &[Nutrition], metric: &Metric) -> Vec<String> {
+    let value = metric.value;
+    let format = |v: f64| {
+        if metric.is_energy {
+            format_calories(v)
+        } else {
+            format_number_with_unit(v, "g")
+        }
+    };
+
+    let base = value(&nutrition[0]);
+    let mut row = vec![label.to_string(), format(base)];
+    for other in &nutrition[1..] {
+        let current = value(other);
+        row.push(format(current));
+        row.push(format_difference(base, current, format));
+    }
+    row
+}
@@ -0,0 +1,145 @@
+use crate::catalog;
+use crate::error::AppResult;
+use clap::{Args, ValueEnum};
+use clap_complete::CompleteEnv;
+use clap_complete::engine::{ArgValueCompleter, CompletionCandidate};
+use clap_complete::env::{Bash, EnvCompleter, Fish, Zsh};
+use std::ffi::OsStr;
+use std::io::{self, Write};
+
+/// Environment variable the generated scripts set when asking for completions
+const COMPLETE_VAR: &str = "COMPLETE";
+const BIN: &str = "nutriterm";
+
+#[derive(Args)]
+pub struct CompletionsArgs {
+    #[arg(value_enum, help = "Shell to generate the completion script for")]
+    shell: CompletionShell,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum CompletionShell {
+    Bash,
+    Zsh,
+    Fish,
+}
+
+/// Print the script that hooks the shell's completion up to nutriterm
+///
+/// The script calls back into nutriterm on every tab press, so recipe names
+/// and ingredient IDs come from the catalog found from the current directory.
+pub fn run(args: &CompletionsArgs) -> AppResult<()> {
+    let mut script = Vec::new();
+    let shell: &dyn EnvCompleter = match args.shell {
+        CompletionShell::Bash => &Bash,
+        CompletionShell::Zsh => &Zsh,
+        CompletionShell::Fish => &Fish,
+    };
+    shell.write_registration(COMPLETE_VAR, BIN, BIN, BIN, &mut script)?;
+
+    let mut script = String::from_utf8_lossy(&script).into_owned();
+    if let CompletionShell::Bash = args.shell {
+        // Let readline quote names with spaces, as zsh and fish do on their own
+        script = script.replace(" -F _clap_complete_", " -o filenames -F _clap_complete_");
+    }
+    io::stdout().write_all(script.as_bytes())?;
+    Ok(())
+}
+
+/// Answer a completion script's callback and exit; does nothing on normal runs
+pub fn answer_callback(command: fn() -> clap::Command) {
+    CompleteEnv::with_factory(command)
+        .var(COMPLETE_VAR)
+        .complete();
+}
+
+/// Completes recipe names
+pub fn recipe_names() -> ArgValueCompleter {
+    ArgValueCompleter::new(|current: &OsStr| {
+        let recipes = catalog::load_recipes().unwrap_or_default();
+        let names = recipes.iter().map(|recipe| (recipe.name.as_str(), None));
+        candidates(names, current, "")
+    })
+}
+
+/// Completes ingredient IDs, described by the ingredient's name
+pub fn ingredient_ids() -> ArgValueCompleter {
+    ArgValueCompleter::new(|current: &OsStr| ingredient_candidates(current, ""))
+}
+
+/// Completes the ID part of `ID=GRAMS` values
+pub fn ingredient_amounts() -> ArgValueCompleter {
+    ArgValueCompleter::new(|current: &OsStr| {
+        if current.to_string_lossy().contains('=') {
+            return Vec::new();
+        }
+        ingredient_candidates(current, "=")
+    })
+}
+
+/// Completes recipe names and ingredient IDs
+pub fn foods() -> ArgValueCompleter {
+    ArgValueCompleter::new(|current: &OsStr| {
+        let catalog = catalog::load().ok();
+        let recipes = catalog.iter().flat_map(|c| &c.recipes);
+        let ingredients = catalog.iter().flat_map(|c| &c.ingredients);
+        let values = recipes
+            .map(|recipe| (recipe.name.as_str(), None))
+            .chain(ingredients.map(|i| (i.id.as_str(), Some(i.name.as_str()))));
+        candidates(values, current, "")
+    })
+}
+
+fn ingredient_candidates(current: &OsStr, suffix: &str) -> Vec<CompletionCandidate> {
+    let ingredients = catalog::load()
+        .map(|catalog| catalog.ingredients)
+        .unwrap_or_default();
+    let ids = ingredients
+        .iter()
+        .map(|ingredient| (ingredient.id.as_str(), Some(ingredient.name.as_str())));
+    candidates(ids, current, suffix)
+}
+
+/// Values starting with what was typed (ignoring case), or containing it when none do
+fn candidates<'a>(
+    values: impl Iterator<Item = (&'a str, Option<&'a str>)>,
+    current: &OsStr,
+    suffix: &str,
+) -> Vec<CompletionCandidate> {
+    let typed = unescape(&current.to_string_lossy()).to_lowercase();
+    let values: Vec<_> = values.collect();
+    let prefixed: Vec<_> = values
+        .iter()
+        .filter(|(value, _)| value.to_lowercase().starts_with(&typed))
+        .collect();
+    let matching = if prefixed.is_empty() {
+        values
+            .iter()
+            .filter(|(value, _)| value.to_lowercase().contains(&typed))
+            .collect()
+    } else {
+        prefixed
+    };
+
+    matching
+        .into_iter()
+        .map(|(value, help)| {
+            CompletionCandidate::new(format!("{}{}", value, suffix))
+                .help(help.map(|help| help.to_string().into()))
+        })
+        .collect()
+}
+
+/// What was typed, without a leading quote or backslash escapes
+fn unescape(typed: &str) -> String {
+    let typed = typed.strip_prefix(['"', '\'']).unwrap_or(typed);
+    let mut result = String::new();
+    let mut chars = typed.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => result.extend(chars.next()),
+            c => result.push(c),
+        }
+    }
+    result
+}
@@ -0,0 +1,255 @@
+use super::display::{
+    Summary, format_calories, format_number_with_unit, render_summary_table, render_target_progress,
+};
+use crate::catalog::items::{DiaryEntry, FoodKind, Ingredient, Nutrition, Recipe};
+use crate::catalog::{self, Catalog};
+use crate::error::{AppError, AppResult};
+use crate::search::ingredients as ingredient_search;
+use crate::search::{find_exact_match, find_substring_matches, parse_search_terms};
+use chrono::{Days, Local, NaiveDate};
+use clap::Args;
+use std::collections::BTreeMap;
+use std::io::{self, Write};
+
+#[derive(Args)]
+pub struct LogArgs {
+    #[arg(
+        add = super::completions::foods(),
+        help = "Recipe or ingredient (name, ID or search terms)"
+    )]
+    food: String,
+    #[arg(
+        value_parser = parse_amount,
+        help = "Amount: grams (250g), portions of a recipe (1.5x), or a plain number (portions for recipes, grams for ingredients)"
+    )]
+    amount: Amount,
+    #[arg(
+        long,
+        value_parser = parse_date,
+        help = "Day eaten: YYYY-MM-DD, today or yesterday [default: today]"
+    )]
+    date: Option<NaiveDate>,
+    #[arg(long, help = "Meal label (e.g., Lunch)")]
+    meal: Option<String>,
+}
+
+#[derive(Args)]
+pub struct ReportArgs {
+    #[arg(
+        long,
+        value_parser = parse_date,
+        help = "First day of the report [default: six days before --to]"
+    )]
+    from: Option<NaiveDate>,
+    #[arg(
+        long,
+        value_parser = parse_date,
+        help = "Last day of the report [default: today]"
+    )]
+    to: Option<NaiveDate>,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Amount {
+    Grams(f64),
+    Portions(f64),
+    /// Portions for recipes, grams for ingredients
+    Plain(f64),
+}
+
+enum Food<'a> {
+    Recipe(&'a Recipe),
+    Ingredient(&'a Ingredient),
+}
+
+pub fn log(args: &LogArgs) -> AppResult<()> {
+    let catalog = catalog::load()?;
+    let food = resolve_food(&catalog, &args.food)?;
+
+    let (name, kind, nutrition) = match (food, args.amount) {
+        (Food::Recipe(recipe), Amount::Grams(grams)) => (
+            &recipe.name,
+            FoodKind::Recipe,
+            portion_of(recipe, recipe.factor_for_portion(grams)),
+        ),
+        (Food::Recipe(recipe), Amount::Portions(portions) | Amount::Plain(portions)) => {
+            (&recipe.name, FoodKind::Recipe, portion_of(recipe, portions))
+        }
+        (Food::Ingredient(ingredient), Amount::Grams(grams) | Amount::Plain(grams)) => (
+            &ingredient.name,
+            FoodKind::Ingredient,
+            ingredient.nutrition_for(grams),
+        ),
+        (Food::Ingredient(ingredient), Amount::Portions(_)) => {
+            return Err(AppError::InvalidArgument {
+                message: format!(
+                    "Portions only apply to recipes; give '{}' in grams (e.g. 100g)",
+                    ingredient.name
+                ),
+            });
+        }
+    };
+
+    let entry = DiaryEntry {
+        date: args.date.unwrap_or_else(today),
+        meal: args.meal.clone(),
+        food: name.clone(),
+        kind,
+        nutrition,
+    };
+    catalog::append_diary_entry(&entry)?;
+
+    println!(
+        "✅ Logged {} of '{}' ({}) on {}",
+        format_number_with_unit(nutrition.grams, "g"),
+        entry.food,
+        format_calories(nutrition.calories),
+        entry.date
+    );
+    Ok(())
+}
+
+pub fn day(date: Option<NaiveDate>, person: Option<&str>) -> AppResult<()> {
+    let date = date.unwrap_or_else(today);
+    let targets = catalog::load_targets(person)?;
+    let entries: Vec<DiaryEntry> = catalog::load_diary()?
+        .into_iter()
+        .filter(|entry| entry.date == date)
+        .collect();
+
+    let mut stdout = io::stdout();
+    writeln!(stdout, "Diary: {}", date)?;
+    writeln!(stdout)?;
+    if entries.is_empty() {
+        writeln!(stdout, "Nothing logged on {}.", date)?;
+    } else {
+        let rows: Vec<(String, Nutrition)> = entries
+            .iter()
+            .map(|entry| {
+                let name = match &entry.meal {
+                    Some(meal) => format!("{}: {}", meal, entry.food),
+                    None => entry.food.clone(),
+                };
+                (name, entry.nutrition)
+            })
+            .collect();
+        render_summary_table(&rows, Summary::Total, &mut stdout)?;
+    }
+
+    // The whole budget is still worth seeing before anything is logged
+    if let Some(targets) = &targets {
+        let totals: Nutrition = entries.iter().map(|entry| entry.nutrition).sum();
+        writeln!(stdout)?;
+        writeln!(stdout, "Daily targets")?;
+        render_target_progress(&totals, targets, &mut stdout)?;
+    }
+    Ok(())
+}
+
+pub fn report(args: &ReportArgs) -> AppResult<()> {
+    let to = args.to.unwrap_or_else(today);
+    let from = args
+        .from
+        .unwrap_or_else(|| to.checked_sub_days(Days::new(6)).unwrap_or(to));
+    if from > to {
+        return Err(AppError::InvalidArgument {
+            message: format!("--from ({}) is after --to ({})", from, to),
+        });
+    }
+
+    let mut totals_by_day: BTreeMap<NaiveDate, Nutrition> = BTreeMap::new();
+    for entry in catalog::load_diary()? {
+        if entry.date >= from && entry.date <= to {
+            *totals_by_day.entry(entry.date).or_default() += entry.nutrition;
+        }
+    }
+
+    let mut stdout = io::stdout();
+    let day_count = (to - from).num_days() + 1;
+    writeln!(stdout, "Report: {} to {}", from, to)?;
+    writeln!(stdout)?;
+    if totals_by_day.is_empty() {
+        writeln!(stdout, "Nothing logged in this period.")?;
+        return Ok(());
+    }
+
+    let rows: Vec<(String, Nutrition)> = totals_by_day
+        .iter()
+        .map(|(date, nutrition)| (date.to_string(), *nutrition))
+        .collect();
+    render_summary_table(&rows, Summary::Average, &mut stdout)?;
+    writeln!(
+        stdout,
+        "Averages are per logged day ({} of {} days logged).",
+        totals_by_day.len(),
+        day_count
+    )?;
+    Ok(())
+}
+
+/// Nutrition of a multiple of a recipe, weighed as the finished dish
+fn portion_of(recipe: &Recipe, factor: f64) -> Nutrition {
+    Nutrition {
+        grams: recipe.finished_grams() * factor,
+        ..recipe.totals() * factor
+    }
+}
+
+/// Exact recipe name, ingredient ID or name, otherwise the only recipe or ingredient matching
+fn resolve_food<'a>(catalog: &'a Catalog, query: &str) -> AppResult<Food<'a>> {
+    if let Some(recipe) = find_exact_match(&catalog.recipes, query) {
+        return Ok(Food::Recipe(recipe));
+    }
+    if let Some(ingredient) = ingredient_search::find_exact_match(&catalog.ingredients, query) {
+        return Ok(Food::Ingredient(ingredient));
+    }
+
+    let recipes = find_substring_matches(&catalog.recipes, &parse_search_terms(query));
+    let ingredients = ingredient_search::find_substring_matches(&catalog.ingredients, query);
+    match (recipes.as_slice(), ingredients.as_slice()) {
+        ([recipe], []) => Ok(Food::Recipe(recipe)),
+        ([], [ingredient]) => Ok(Food::Ingredient(ingredient)),
+        _ => Err(AppError::FoodNotFound {
+            query: query.to_string(),
+            candidates: recipes
+                .iter()
+                .map(|r| r.name.clone())
+                .chain(ingredients.iter().map(|i| i.id.clone()))
+                .collect(),
+        }),
+    }
+}
+
+fn today() -> NaiveDate {
+    Local::now().date_naive()
+}
+
+pub(crate) fn parse_date(value: &str) -> Result<NaiveDate, String> {
+    match value.trim().to_lowercase().as_str() {
+        "today" => Ok(today()),
+        "yesterday" => today()
+            .checked_sub_days(Days::new(1))
+            .ok_or_else(|| "date out of range".to_string()),
+        other => NaiveDate::parse_from_str(other, "%Y-%m-%d")
+            .map_err(|_| format!("expected YYYY-MM-DD, today or yesterday, got '{}'", value)),
+    }
+}
+
+fn parse_amount(value: &str) -> Result<Amount, String> {
+    let normalized = value.trim().to_lowercase();
+    let (number, amount): (&str, fn(f64) -> Amount) =
+        if let Some(number) = normalized.strip_suffix('g') {
+            (number, Amount::Grams)
+        } else if let Some(number) = normalized.strip_suffix('x') {
+            (number, Amount::Portions)
+        } else {
+            (&normalized, Amount::Plain)
+        };
+    number
+        .trim()
+        .parse::<f64>()
+        .ok()
+        .filter(|n| n.is_finite() && *n > 0.0)
+        .map(amount)
+        .ok_or_else(|| format!("expected an amount like 250g, 1.5x or 2, got '{}'", value))
+}
@@ -0,0 +1,21 @@
+use crate::catalog::FileChange;
+use similar::TextDiff;
+use std::io::{self, Write};
+
+/// Write a unified diff of a planned file change
+pub(crate) fn write_unified_diff<W: Write>(change: &FileChange, writer: &mut W) -> io::Result<()> {
+    let filename = change
+        .path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+
+    let diff = TextDiff::from_lines(&change.original, &change.updated);
+    write!(
+        writer,
+        "{}",
+        diff.unified_diff()
+            .context_radius(2)
+            .header(&format!("a/{}", filename), &format!("b/{}", filename))
+    )
+}
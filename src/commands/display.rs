@@ -0,0 +1,443 @@
+use crate::catalog::items::{
+    DailyTargets, EnergySplit, Nutrient, Nutrition, TargetStatus, WeightedIngredient,
+};
+use std::io::Write;
+use tabled::{
+    Table, Tabled,
+    builder::Builder,
+    settings::{Alignment, Color, Format, Modify, Padding, Style, object::Rows},
+};
+
+#[derive(Tabled)]
+struct NutritionRow {
+    #[tabled(rename = "Name")]
+    name: String,
+    #[tabled(rename = "Weight")]
+    weight: String,
+    #[tabled(rename = "Net carbs")]
+    carbs: String,
+    #[tabled(rename = "Protein")]
+    protein: String,
+    #[tabled(rename = "Fat")]
+    fat: String,
+    #[tabled(rename = "Fiber")]
+    fiber: String,
+    #[tabled(rename = "Calories")]
+    calories: String,
+}
+
+#[derive(Tabled)]
+struct BreakdownRow {
+    #[tabled(inline)]
+    nutrition: NutritionRow,
+    #[tabled(rename = "% kcal")]
+    calorie_share: String,
+    #[tabled(rename = "% protein")]
+    protein_share: String,
+}
+
+/// Optional extras for the nutrition table
+#[derive(Default)]
+pub(crate) struct TableOptions<'a> {
+    /// Ingredient IDs whose rows are emphasized and marked with `*`
+    pub highlight: &'a [&'a str],
+    /// Adds a "Per 100 g" row below the total, for a finished dish of this weight
+    pub per_100g_of: Option<f64>,
+    /// Adds a "% of daily target" row below the total
+    pub daily_targets: Option<&'a DailyTargets>,
+    /// Adds each row's share of calories and protein, marks the largest calorie
+    /// source with `★`, and prints the energy split below the table
+    pub energy_breakdown: bool,
+}
+
+pub(crate) fn render_nutrition_table<W: Write>(
+    recipe: &[WeightedIngredient],
+    options: &TableOptions,
+    writer: &mut W,
+) -> std::io::Result<()> {
+    let mut rows = Vec::new();
+    let mut highlighted_rows = Vec::new();
+
+    for ingredient in recipe {
+        let mut name = truncate_name(&ingredient.ingredient.name);
+        if options
+            .highlight
+            .contains(&ingredient.ingredient.id.as_str())
+        {
+            name.push_str(" *");
+            // Row 0 is the header
+            highlighted_rows.push(rows.len() + 1);
+        }
+        rows.push((name, ingredient.nutrition()));
+    }
+
+    render_rows_with_summary(&rows, Summary::Total, &highlighted_rows, options, writer)?;
+
+    if options.energy_breakdown {
+        let totals: Nutrition = rows.iter().map(|(_, nutrition)| *nutrition).sum();
+        if let Some(split) = totals.energy_split() {
+            writeln!(writer, "{}", format_energy_split(&split))?;
+        }
+    }
+    Ok(())
+}
+
+/// Header and unstyled cells of the recipe nutrition table, ending with the total
+///
+/// For front ends that draw their own tables, such as the TUI.
+pub(crate) fn nutrition_table_cells(
+    recipe: &[WeightedIngredient],
+) -> (Vec<String>, Vec<Vec<String>>) {
+    let mut cells: Vec<Vec<String>> = recipe
+        .iter()
+        .map(|entry| nutrition_cells(&entry.ingredient.name, &entry.nutrition()))
+        .collect();
+    let totals: Nutrition = recipe.iter().map(|entry| entry.nutrition()).sum();
+    cells.push(nutrition_cells("Total", &totals));
+    (nutrition_headers(), cells)
+}
+
+/// Column headers of the nutrition table, starting with "Name"
+pub(crate) fn nutrition_headers() -> Vec<String> {
+    NutritionRow::headers()
+        .into_iter()
+        .map(|header| header.into_owned())
+        .collect()
+}
+
+/// One row of the nutrition table as unstyled, rounded cells
+pub(crate) fn nutrition_cells(name: &str, nutrition: &Nutrition) -> Vec<String> {
+    nutrition_row(name.to_string(), nutrition)
+        .fields()
+        .into_iter()
+        .map(|cell| cell.into_owned())
+        .collect()
+}
+
+/// Last row of a summary table
+#[derive(Clone, Copy)]
+pub(crate) enum Summary {
+    Total,
+    Average,
+}
+
+/// Named nutrition rows followed by their total or average, styled like the recipe table
+pub(crate) fn render_summary_table<W: Write>(
+    rows: &[(String, Nutrition)],
+    summary: Summary,
+    writer: &mut W,
+) -> std::io::Result<()> {
+    let rows: Vec<(String, Nutrition)> = rows
+        .iter()
+        .map(|(name, nutrition)| (truncate_name(name), *nutrition))
+        .collect();
+    render_rows_with_summary(&rows, summary, &[], &TableOptions::default(), writer)
+}
+
+fn render_rows_with_summary<W: Write>(
+    entries: &[(String, Nutrition)],
+    summary: Summary,
+    highlighted_rows: &[usize],
+    options: &TableOptions,
+    writer: &mut W,
+) -> std::io::Result<()> {
+    let mut rows: Vec<NutritionRow> = entries
+        .iter()
+        .map(|(name, nutrition)| nutrition_row(name.clone(), nutrition))
+        .collect();
+    let totals: Nutrition = entries.iter().map(|(_, nutrition)| *nutrition).sum();
+    match summary {
+        Summary::Total => rows.push(nutrition_row("Total".to_string(), &totals)),
+        Summary::Average => {
+            let count = entries.len().max(1) as f64;
+            rows.push(nutrition_row(
+                "Average".to_string(),
+                &(totals * (1.0 / count)),
+            ));
+        }
+    }
+
+    let summary_row = rows.len();
+    if let Some(finished_grams) = options.per_100g_of.filter(|grams| *grams > 0.0) {
+        let per_100g = Nutrition {
+            grams: 100.0,
+            ..totals * (100.0 / finished_grams)
+        };
+        rows.push(nutrition_row("Per 100 g".to_string(), &per_100g));
+    }
+    if let Some(targets) = options.daily_targets {
+        rows.push(target_share_row(&totals, targets));
+    }
+
+    let mut top_contributor = None;
+    let mut table = if options.energy_breakdown {
+        let share = |value: f64, total: f64| {
+            if total > 0.0 {
+                format!("{:.0}%", value / total * 100.0)
+            } else {
+                String::new()
+            }
+        };
+        let mut calorie_shares: Vec<String> = entries
+            .iter()
+            .map(|(_, n)| share(n.calories, totals.calories))
+            .collect();
+        let mut protein_shares: Vec<String> = entries
+            .iter()
+            .map(|(_, n)| share(n.protein, totals.protein))
+            .collect();
+        if entries.len() > 1 && totals.calories > 0.0 {
+            let top = (0..entries.len())
+                .max_by(|&a, &b| entries[a].1.calories.total_cmp(&entries[b].1.calories))
+                .unwrap_or(0);
+            calorie_shares[top].push_str(" ★");
+            // Row 0 is the header
+            top_contributor = Some(top + 1);
+        }
+        calorie_shares.push(share(totals.calories, totals.calories));
+        protein_shares.push(share(totals.protein, totals.protein));
+
+        let breakdown: Vec<BreakdownRow> = rows
+            .into_iter()
+            .enumerate()
+            .map(|(index, nutrition)| BreakdownRow {
+                nutrition,
+                calorie_share: calorie_shares.get(index).cloned().unwrap_or_default(),
+                protein_share: protein_shares.get(index).cloned().unwrap_or_default(),
+            })
+            .collect();
+        Table::new(&breakdown)
+    } else {
+        Table::new(&rows)
+    };
+    table
+        .with(Style::rounded())
+        .with(
+            Modify::new(Rows::new(0..=0))
+                .with(Color::FG_CYAN)
+                .with(Format::content(|s| format!(" {} ", s))),
+        )
+        .with(Modify::new(Rows::new(1..summary_row)).with(Alignment::right()))
+        .with(
+            Modify::new(Rows::new(summary_row..))
+                .with(Color::FG_BRIGHT_WHITE)
+                .with(Alignment::right()),
+        )
+        .with(Padding::new(1, 1, 0, 0));
+    for &row in highlighted_rows {
+        table.with(Modify::new(Rows::new(row..=row)).with(Color::FG_YELLOW));
+    }
+    if let Some(row) = top_contributor {
+        table.with(Modify::new(Rows::new(row..=row)).with(Color::FG_MAGENTA));
+    }
+
+    writeln!(writer, "{}", table)?;
+
+    Ok(())
+}
+
+/// Share of each daily target that the totals make up; blank where there is no target
+fn target_share_row(totals: &Nutrition, targets: &DailyTargets) -> NutritionRow {
+    let share = |nutrient: Nutrient| match targets.get(nutrient).and_then(|t| t.reference()) {
+        Some(reference) if reference > 0.0 => {
+            format!("{:.0}%", nutrient.value(totals) / reference * 100.0)
+        }
+        _ => String::new(),
+    };
+    NutritionRow {
+        name: "% of daily target".to_string(),
+        weight: String::new(),
+        carbs: share(Nutrient::Carbs),
+        protein: share(Nutrient::Protein),
+        fat: share(Nutrient::Fat),
+        fiber: share(Nutrient::Fiber),
+        calories: share(Nutrient::Calories),
+    }
+}
+
+/// Each targeted nutrient's total against its target, with the remaining budget
+///
+/// Rows are red when over a maximum, yellow when under a minimum and green otherwise.
+pub(crate) fn render_target_progress<W: Write>(
+    totals: &Nutrition,
+    targets: &DailyTargets,
+    writer: &mut W,
+) -> std::io::Result<()> {
+    let mut builder = Builder::default();
+    builder.push_record(["Nutrient", "Target", "Total", "Remaining"]);
+    let mut colors = Vec::new();
+
+    for (nutrient, target) in &targets.targets {
+        let format = |value: f64| format_nutrient(*nutrient, value);
+        let value = nutrient.value(totals);
+        let (remaining, color) = match target.status(value) {
+            TargetStatus::Over(by) => (format!("▲ {} over", format(by)), Color::FG_RED),
+            TargetStatus::Under(by) => (format!("▼ {} to go", format(by)), Color::FG_YELLOW),
+            TargetStatus::Within { left: Some(left) } => {
+                (format!("✓ {} left", format(left)), Color::FG_GREEN)
+            }
+            TargetStatus::Within { left: None } => ("✓ reached".to_string(), Color::FG_GREEN),
+        };
+        let range = match (target.min, target.max) {
+            (Some(min), Some(max)) => format!("{} – {}", format(min), format(max)),
+            (Some(min), None) => format!("≥ {}", format(min)),
+            (None, Some(max)) => format!("≤ {}", format(max)),
+            (None, None) => String::new(),
+        };
+        builder.push_record([
+            nutrient.label().to_string(),
+            range,
+            format(value),
+            remaining,
+        ]);
+        colors.push(color);
+    }
+
+    let mut table = builder.build();
+    apply_standard_style(&mut table);
+    for (index, color) in colors.into_iter().enumerate() {
+        // Row 0 is the header
+        table.with(Modify::new(Rows::new(index + 1..=index + 1)).with(color));
+    }
+    writeln!(writer, "{}", table)
+}
+
+fn format_nutrient(nutrient: Nutrient, value: f64) -> String {
+    match nutrient {
+        Nutrient::Calories => format_calories(value),
+        _ => format_number_with_unit(value, "g"),
+    }
+}
+
+/// e.g. "Energy split: 31% protein, 42% fat, 27% net carbs"
+pub(crate) fn format_energy_split(split: &EnergySplit) -> String {
+    format!(
+        "Energy split: {:.0}% protein, {:.0}% fat, {:.0}% net carbs",
+        split.protein * 100.0,
+        split.fat * 100.0,
+        split.carbs * 100.0
+    )
+}
+
+/// Render rows with the standard look
+pub(crate) fn render_table<R: Tabled, W: Write>(rows: &[R], writer: &mut W) -> std::io::Result<()> {
+    let mut table = Table::new(rows);
+    apply_standard_style(&mut table);
+    writeln!(writer, "{}", table)
+}
+
+/// Rounded borders, cyan header, right-aligned values
+pub(crate) fn apply_standard_style(table: &mut Table) {
+    table
+        .with(Style::rounded())
+        .with(
+            Modify::new(Rows::new(0..=0))
+                .with(Color::FG_CYAN)
+                .with(Format::content(|s| format!(" {} ", s))),
+        )
+        .with(Modify::new(Rows::new(1..)).with(Alignment::right()))
+        .with(Padding::new(1, 1, 0, 0));
+}
+
+fn nutrition_row(name: String, nutrition: &Nutrition) -> NutritionRow {
+    NutritionRow {
+        name,
+        weight: format_number_with_unit(nutrition.grams, "g"),
+        carbs: format_number_with_unit(nutrition.carbs, "g"),
+        protein: format_number_with_unit(nutrition.protein, "g"),
+        fat: format_number_with_unit(nutrition.fat, "g"),
+        fiber: format_number_with_unit(nutrition.fiber, "g"),
+        calories: format_calories(nutrition.calories),
+    }
+}
+
+/// Shorten long names so tables stay readable in narrow terminals
+pub(crate) fn truncate_name(name: &str) -> String {
+    if name.len() > 25 {
+        format!("{}…", name.chars().take(24).collect::<String>())
+    } else {
+        name.to_string()
+    }
+}
+
+pub(crate) fn format_number_with_unit(value: f64, unit: &str) -> String {
+    if value <= 0.01 {
+        format!("0 {}", unit)
+    } else if value >= 1000.0 {
+        format!("{} {}", format_large_number(value), unit)
+    } else {
+        format!("{:.1} {}", value, unit)
+    }
+}
+
+/// Absolute and relative difference, e.g. "+12.5 g (+8%)"
+pub(crate) fn format_difference(base: f64, current: f64, format: impl Fn(f64) -> String) -> String {
+    let difference = current - base;
+    let sign = if difference > 0.01 {
+        "+"
+    } else if difference < -0.01 {
+        "-"
+    } else {
+        ""
+    };
+    let absolute = format!("{}{}", sign, format(difference.abs()));
+
+    if sign.is_empty() {
+        absolute
+    } else if base.abs() > 0.01 {
+        format!("{} ({:+.0}%)", absolute, difference / base * 100.0)
+    } else {
+        absolute
+    }
+}
+
+pub(crate) fn format_calories(calories: f64) -> String {
+    if calories <= 0.01 {
+        "0 kcal".to_string()
+    } else if calories >= 1000.0 {
+        format!(
+            "{} kcal",
+            add_thousand_separators(&format!("{:.0}", calories))
+        )
+    } else {
+        format!("{:.0} kcal", calories)
+    }
+}
+
+fn format_large_number(value: f64) -> String {
+    if value >= 1000.0 {
+        let formatted = format!("{:.1}", value);
+        add_thousand_separators(&formatted)
+    } else {
+        format!("{:.1}", value)
+    }
+}
+
+fn add_thousand_separators(s: &str) -> String {
+    let parts: Vec<&str> = s.split('.').collect();
+    let integer_part = parts[0];
+    let decimal_part = if parts.len() > 1 { parts[1] } else { "" };
+
+    let mut result = String::new();
+    for (i, c) in integer_part.chars().rev().enumerate() {
+        if i > 0 && i % 3 == 0 {
+            result.push(',');
+        }
+        result.push(c);
+    }
+
+    let formatted_integer: String = result.chars().rev().collect();
+    if decimal_part.is_empty() || decimal_part == "0" {
+        formatted_integer
+    } else {
+        format!("{}.{}", formatted_integer, decimal_part)
+    }
+}
+
+/// Escape text for use in HTML element content and attribute values
+pub(crate) fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
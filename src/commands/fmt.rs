@@ -0,0 +1,63 @@
+use super::diff::write_unified_diff;
+use crate::catalog::{self, Edit, FileChange, FormatOptions};
+use crate::error::{AppError, AppResult};
+use clap::Args;
+use std::io;
+
+#[derive(Args)]
+pub struct FmtArgs {
+    #[arg(
+        long,
+        help = "Only check formatting; show a diff and fail if files would change"
+    )]
+    check: bool,
+    #[arg(long, help = "Sort ingredients by ID")]
+    sort_ingredients: bool,
+    #[arg(long, help = "Sort recipes by name")]
+    sort_recipes: bool,
+    #[arg(
+        long,
+        help = "Write numbers in their shortest form (e.g. 100.0 becomes 100)"
+    )]
+    normalize_numbers: bool,
+}
+
+pub fn run(args: &FmtArgs) -> AppResult<()> {
+    let options = FormatOptions {
+        sort_ingredients: args.sort_ingredients,
+        sort_recipes: args.sort_recipes,
+        normalize_numbers: args.normalize_numbers,
+    };
+    let changes = catalog::plan_edit(&Edit::Format(options))?;
+
+    if args.check {
+        if changes.is_empty() {
+            println!("✅ Catalog files are formatted");
+            return Ok(());
+        }
+        let mut stdout = io::stdout();
+        for change in &changes {
+            write_unified_diff(change, &mut stdout)?;
+        }
+        return Err(AppError::FormatCheckFailed {
+            files: changes.iter().map(file_name).collect(),
+        });
+    }
+
+    catalog::write_changes(&changes)?;
+    if changes.is_empty() {
+        println!("✅ Catalog files are already formatted");
+    }
+    for change in &changes {
+        println!("✅ Formatted {}", file_name(change));
+    }
+    Ok(())
+}
+
+fn file_name(change: &FileChange) -> String {
+    change
+        .path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default()
+}
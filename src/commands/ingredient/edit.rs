@@ -0,0 +1,116 @@
+use crate::catalog::items::Ingredient;
+use crate::catalog::{self, Edit};
+use crate::commands::completions::ingredient_ids;
+use crate::commands::diff::write_unified_diff;
+use crate::error::AppResult;
+use clap::Subcommand;
+use std::io;
+
+#[derive(Subcommand)]
+pub enum IngredientCommand {
+    #[command(about = "Add a new ingredient to ingredients.jsonc")]
+    Add {
+        #[arg(help = "Stable ingredient ID used in recipes (e.g., brown_rice)")]
+        id: String,
+        #[arg(long, help = "Human-readable name")]
+        name: String,
+        #[arg(long, value_name = "GRAMS", help = "Net carbs per 100 g")]
+        carbs: f64,
+        #[arg(long, value_name = "GRAMS", help = "Protein per 100 g")]
+        protein: f64,
+        #[arg(long, value_name = "GRAMS", help = "Fat per 100 g")]
+        fat: f64,
+        #[arg(long, value_name = "GRAMS", help = "Fiber per 100 g")]
+        fiber: f64,
+        #[arg(long, help = "Store section for shopping lists (e.g., Produce)")]
+        aisle: Option<String>,
+        #[arg(long, value_name = "GRAMS", help = "Weight of one package as sold")]
+        package_grams: Option<f64>,
+    },
+    #[command(about = "Rename an ingredient ID everywhere in the catalog")]
+    Rename {
+        #[arg(add = ingredient_ids(), help = "Current ingredient ID")]
+        old_id: String,
+        #[arg(help = "New ingredient ID")]
+        new_id: String,
+        #[arg(long, help = "Show the changes as a diff without writing them")]
+        dry_run: bool,
+    },
+}
+
+pub fn run(command: &IngredientCommand) -> AppResult<()> {
+    match command {
+        IngredientCommand::Add {
+            id,
+            name,
+            carbs,
+            protein,
+            fat,
+            fiber,
+            aisle,
+            package_grams,
+        } => {
+            let edit = Edit::AddIngredient(Ingredient {
+                id: id.clone(),
+                name: name.clone(),
+                carbs_per_100g: *carbs,
+                protein_per_100g: *protein,
+                fat_per_100g: *fat,
+                fiber_per_100g: *fiber,
+                aisle: aisle.clone(),
+                package_grams: *package_grams,
+            });
+            let changes = catalog::plan_edit(&edit)?;
+            catalog::write_changes(&changes)?;
+            println!("✅ Added ingredient '{}' to ingredients.jsonc", id);
+        }
+        IngredientCommand::Rename {
+            old_id,
+            new_id,
+            dry_run,
+        } => {
+            let edit = Edit::RenameIngredient {
+                old_id: old_id.clone(),
+                new_id: new_id.clone(),
+            };
+            let changes = catalog::plan_edit(&edit)?;
+
+            if *dry_run {
+                let mut stdout = io::stdout();
+                for change in &changes {
+                    write_unified_diff(change, &mut stdout)?;
+                }
+                println!("Dry run: no files were changed.");
+                return Ok(());
+            }
+
+            let references = count_references(old_id)?;
+            catalog::write_changes(&changes)?;
+            let noun = if references == 1 {
+                "reference"
+            } else {
+                "references"
+            };
+            let pantry = if changes.iter().any(|c| c.path.ends_with("pantry.jsonc")) {
+                ", pantry updated"
+            } else {
+                ""
+            };
+            println!(
+                "✅ Renamed '{}' to '{}' ({} recipe {} updated{})",
+                old_id, new_id, references, noun, pantry
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Number of recipe ingredient entries pointing at the ingredient
+fn count_references(ingredient_id: &str) -> AppResult<usize> {
+    let recipes = catalog::load_recipes()?;
+    Ok(recipes
+        .iter()
+        .flat_map(|recipe| &recipe.ingredients)
+        .filter(|i| i.ingredient.id == ingredient_id)
+        .count())
+}
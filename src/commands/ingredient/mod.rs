@@ -0,0 +1,140 @@
+mod edit;
+
+pub use edit::{IngredientCommand, run as run_edit};
+
+use super::display::{format_calories, format_energy_split, format_number_with_unit, render_table};
+use crate::catalog::items::{Ingredient, Recipe};
+use crate::catalog::{self, Catalog};
+use crate::error::AppResult;
+use crate::search::ingredients::{find_exact_match, find_substring_matches};
+use crate::utils::suggestions::find_best_suggestion;
+use std::io::{self, Write};
+use tabled::Tabled;
+
+#[derive(Tabled)]
+struct Per100gRow {
+    #[tabled(rename = "Per")]
+    per: String,
+    #[tabled(rename = "Net carbs")]
+    carbs: String,
+    #[tabled(rename = "Protein")]
+    protein: String,
+    #[tabled(rename = "Fat")]
+    fat: String,
+    #[tabled(rename = "Fiber")]
+    fiber: String,
+    #[tabled(rename = "Calories")]
+    calories: String,
+}
+
+pub fn run(query: &str) -> AppResult<()> {
+    lookup(&catalog::load()?, query)
+}
+
+/// Show the ingredient with this ID or name, or the one the query matches
+pub(crate) fn lookup(catalog: &Catalog, query: &str) -> AppResult<()> {
+    let mut stdout = io::stdout();
+
+    if let Some(ingredient) = find_exact_match(&catalog.ingredients, query) {
+        render_ingredient(catalog, ingredient, &mut stdout)?;
+        return Ok(());
+    }
+
+    let matches = find_substring_matches(&catalog.ingredients, query);
+
+    match matches.len() {
+        0 => {
+            let ids: Vec<String> = catalog.ingredients.iter().map(|i| i.id.clone()).collect();
+            match find_best_suggestion(query, &ids) {
+                Some(suggestion) => {
+                    println!("No matches for '{}'. Did you mean '{}'?", query, suggestion)
+                }
+                None => println!("No matches for '{}'", query),
+            }
+        }
+        1 => render_ingredient(catalog, matches[0], &mut stdout)?,
+        _ => {
+            let ingredient_list = matches
+                .iter()
+                .map(|i| format!("- {}: {}", i.id, i.name))
+                .collect::<Vec<_>>()
+                .join("\n");
+            println!(
+                "Multiple ingredients found for '{}' ({} matches):\n{}\n\nPlease be more specific with your search term.",
+                query,
+                matches.len(),
+                ingredient_list
+            );
+        }
+    }
+    Ok(())
+}
+
+fn render_ingredient<W: Write>(
+    catalog: &Catalog,
+    ingredient: &Ingredient,
+    writer: &mut W,
+) -> io::Result<()> {
+    writeln!(writer, "Ingredient: {}", ingredient.name)?;
+    writeln!(writer, "ID: {}", ingredient.id)?;
+    writeln!(writer)?;
+
+    let per_100g = ingredient.nutrition_for(100.0);
+    render_table(
+        &[Per100gRow {
+            per: format_number_with_unit(per_100g.grams, "g"),
+            carbs: format_number_with_unit(per_100g.carbs, "g"),
+            protein: format_number_with_unit(per_100g.protein, "g"),
+            fat: format_number_with_unit(per_100g.fat, "g"),
+            fiber: format_number_with_unit(per_100g.fiber, "g"),
+            calories: format_calories(per_100g.calories),
+        }],
+        writer,
+    )?;
+
+    if let Some(split) = per_100g.energy_split() {
+        writeln!(writer, "{}", format_energy_split(&split))?;
+    }
+    writeln!(writer)?;
+
+    let usages = find_usages(&catalog.recipes, &ingredient.id);
+    if usages.is_empty() {
+        writeln!(writer, "Not used in any recipe.")?;
+    } else {
+        let noun = if usages.len() == 1 {
+            "recipe"
+        } else {
+            "recipes"
+        };
+        writeln!(writer, "Used in {} {}:", usages.len(), noun)?;
+        for (recipe, grams) in usages {
+            writeln!(
+                writer,
+                "- {}: {}",
+                recipe.name,
+                format_number_with_unit(grams, "g")
+            )?;
+        }
+    }
+    Ok(())
+}
+
+/// Recipes using the ingredient, with the total grams each one uses
+pub(crate) fn find_usages<'a>(
+    recipes: &'a [Recipe],
+    ingredient_id: &str,
+) -> Vec<(&'a Recipe, f64)> {
+    recipes
+        .iter()
+        .filter(|recipe| recipe.uses_ingredient(ingredient_id))
+        .map(|recipe| {
+            let grams = recipe
+                .ingredients
+                .iter()
+                .filter(|i| i.ingredient.id == ingredient_id)
+                .map(|i| i.grams)
+                .sum();
+            (recipe, grams)
+        })
+        .collect()
+}
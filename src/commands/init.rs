@@ -19,7 +19,7 @@ pub fn run() -> AppResult<()> {
     crate::catalog::initialize(&current_dir)?;
 
     println!("✅ Initialized recipe catalog in {}", current_dir.display());
-    println!("📄 Created schemas, recipes, and ingredients files");
+    println!("📄 Created schemas, recipes, ingredients, plans, and targets files");
     println!("🍽️  Ready to use!");
     Ok(())
 }
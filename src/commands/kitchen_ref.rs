@@ -1,37 +1,319 @@
+use super::display::escape_html;
+use super::recipe::parse_factor;
+use super::watch;
 use crate::catalog;
-use crate::error::AppResult;
+use crate::catalog::items::Recipe;
+use crate::error::{AppError, AppResult};
+use crate::search::{find_exact_match, find_substring_matches, parse_search_terms};
+use clap::{Args, ValueEnum};
+use std::collections::HashSet;
+use std::fmt::Write;
+use std::fs;
+use std::path::{Path, PathBuf};
 
-pub fn run() -> AppResult<()> {
-    let recipes = catalog::load_recipes()?;
+const DEFAULT_TEMPLATE: &str = include_str!("kitchen_ref.template.html");
+const STYLE: &str = include_str!("kitchen_ref.css");
 
-    println!("<!DOCTYPE html>");
-    println!("<html>");
-    println!("<head><title>Kitchen Reference</title></head>");
-    println!("<body>");
-    println!("<h1>Kitchen Reference</h1>");
-    println!();
+/// Placeholders a template may use; `{{recipes}}` is required
+const PLACEHOLDERS: [&str; 4] = ["title", "style", "contents", "recipes"];
 
-    for recipe in &recipes {
-        println!("<h2>{}</h2>", recipe.name);
-        println!("<ul>");
+const TITLE: &str = "Kitchen Reference";
 
-        for ingredient in &recipe.ingredients {
+#[derive(Args)]
+pub struct KitchenRefArgs {
+    #[arg(
+        add = super::completions::recipe_names(),
+        help = "Recipes to include, by name or search terms matching one or more recipes (default: all)"
+    )]
+    recipes: Vec<String>,
+    #[arg(long, help = "Only recipes with this tag (repeatable)")]
+    tag: Vec<String>,
+    #[arg(long, value_enum, default_value_t = SortKey::File, help = "Recipe order")]
+    sort: SortKey,
+    #[arg(
+        long,
+        value_parser = parse_factor,
+        help = "Multiply all weights by a factor, for batch cooking (e.g., 3)"
+    )]
+    scale: Option<f64>,
+    #[arg(
+        short,
+        long,
+        help = "Write the HTML to this file instead of printing it"
+    )]
+    output: Option<PathBuf>,
+    #[arg(
+        long,
+        requires = "output",
+        help = "Regenerate the file whenever the catalog files are saved"
+    )]
+    watch: bool,
+    #[arg(
+        long,
+        help = "HTML template with {{title}}, {{style}}, {{contents}} and {{recipes}} placeholders"
+    )]
+    template: Option<PathBuf>,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum SortKey {
+    /// As in recipes.jsonc
+    File,
+    Name,
+    /// By first tag, untagged recipes last
+    Tag,
+}
+
+pub fn run(args: &KitchenRefArgs) -> AppResult<()> {
+    match (&args.output, args.watch) {
+        // The template is read on every regeneration, so saving it shows up too
+        (Some(output), true) => watch::watch(args.template.iter().cloned().collect(), || {
+            regenerate(args, output)
+        }),
+        (Some(output), false) => {
+            fs::write(output, render_selection(args)?)?;
+            Ok(())
+        }
+        (None, _) => {
+            print!("{}", render_selection(args)?);
+            Ok(())
+        }
+    }
+}
+
+/// Write the reference again, or a page showing the error so the browser has it too
+fn regenerate(args: &KitchenRefArgs, output: &Path) -> AppResult<()> {
+    match render_selection(args) {
+        Ok(html) => {
+            fs::write(output, html)?;
             println!(
-                "<li>{:.1} g  {}</li>",
-                ingredient.grams, ingredient.ingredient.name
+                "{} Wrote {}",
+                chrono::Local::now().format("%H:%M:%S"),
+                output.display()
+            );
+            Ok(())
+        }
+        Err(e) => {
+            fs::write(output, render_error_html(&e))?;
+            Err(e)
+        }
+    }
+}
+
+fn render_selection(args: &KitchenRefArgs) -> AppResult<String> {
+    let template = match &args.template {
+        Some(path) => load_template(path)?,
+        None => DEFAULT_TEMPLATE.to_string(),
+    };
+    let recipes = select(&catalog::load_recipes()?, args)?;
+    Ok(match args.scale {
+        Some(factor) => {
+            let scaled: Vec<Recipe> = recipes.iter().map(|r| r.scaled(factor)).collect();
+            render(&scaled, &format!("{} (×{:.2})", TITLE, factor), &template)
+        }
+        None => render(&recipes, TITLE, &template),
+    })
+}
+
+/// The chosen recipes, filtered by tag and in the requested order
+fn select(recipes: &[Recipe], args: &KitchenRefArgs) -> AppResult<Vec<Recipe>> {
+    // Positions in the file, so file order survives picking recipes in any order
+    let mut chosen: Vec<usize> = Vec::new();
+    if args.recipes.is_empty() {
+        chosen.extend(0..recipes.len());
+    }
+    for query in &args.recipes {
+        let matches = match find_exact_match(recipes, query) {
+            Some(recipe) => vec![recipe],
+            None => find_substring_matches(recipes, &parse_search_terms(query)),
+        };
+        if matches.is_empty() {
+            return Err(AppError::RecipeNotFound {
+                query: query.clone(),
+                candidates: Vec::new(),
+            });
+        }
+        for recipe in matches {
+            let index = recipes
+                .iter()
+                .position(|r| std::ptr::eq(r, recipe))
+                .expect("matches come from the same recipes");
+            if !chosen.contains(&index) {
+                chosen.push(index);
+            }
+        }
+    }
+    chosen.retain(|&index| args.tag.iter().all(|tag| recipes[index].has_tag(tag)));
+
+    if chosen.is_empty() && !args.tag.is_empty() {
+        let which = if args.recipes.is_empty() {
+            "No recipes are"
+        } else {
+            "None of the chosen recipes are"
+        };
+        return Err(AppError::InvalidArgument {
+            message: format!("{} tagged {}", which, args.tag.join(" and ")),
+        });
+    }
+
+    match args.sort {
+        SortKey::File => chosen.sort(),
+        SortKey::Name => chosen.sort_by_key(|&index| recipes[index].name.to_lowercase()),
+        SortKey::Tag => chosen.sort_by_key(|&index| {
+            let first_tag = recipes[index].tags.first();
+            (
+                first_tag.is_none(),
+                first_tag.map(|tag| tag.to_lowercase()),
+                index,
+            )
+        }),
+    }
+    Ok(chosen
+        .into_iter()
+        .map(|index| recipes[index].clone())
+        .collect())
+}
+
+/// Read a user template, rejecting unknown placeholders so typos don't end up on the page
+fn load_template(path: &Path) -> AppResult<String> {
+    let template = fs::read_to_string(path).map_err(|e| AppError::FileUnreadable {
+        path: path.to_path_buf(),
+        io_error: e.to_string(),
+    })?;
+    let invalid = |problem: String| AppError::InvalidArgument {
+        message: format!(
+            "Template {} {}\n\nAvailable placeholders: {}",
+            path.display(),
+            problem,
+            PLACEHOLDERS
+                .map(|name| format!("{{{{{}}}}}", name))
+                .join(", ")
+        ),
+    };
+
+    let mut has_recipes = false;
+    for name in placeholders(&template) {
+        match name {
+            "recipes" => has_recipes = true,
+            _ if PLACEHOLDERS.contains(&name) => {}
+            _ => {
+                return Err(invalid(format!(
+                    "uses unknown placeholder {{{{{}}}}}",
+                    name
+                )));
+            }
+        }
+    }
+    if !has_recipes {
+        return Err(invalid("has no {{recipes}} placeholder".to_string()));
+    }
+    Ok(template)
+}
+
+/// Names of the `{{name}}` placeholders in a template, in order
+fn placeholders(template: &str) -> impl Iterator<Item = &str> {
+    template
+        .split("{{")
+        .skip(1)
+        .filter_map(|rest| rest.split_once("}}").map(|(name, _)| name.trim()))
+}
+
+/// The kitchen reference page in the default layout
+pub(crate) fn render_html(recipes: &[Recipe]) -> String {
+    render(recipes, TITLE, DEFAULT_TEMPLATE)
+}
+
+fn render(recipes: &[Recipe], title: &str, template: &str) -> String {
+    let anchors = anchors(recipes);
+
+    let mut contents = String::from("<ol>\n");
+    for (recipe, anchor) in recipes.iter().zip(&anchors) {
+        let _ = writeln!(
+            contents,
+            "<li><a href=\"#{}\">{}</a></li>",
+            anchor,
+            escape_html(&recipe.name)
+        );
+    }
+    contents.push_str("</ol>\n");
+
+    let mut sections = String::new();
+    for (recipe, anchor) in recipes.iter().zip(&anchors) {
+        let _ = writeln!(sections, "<section class=\"recipe\" id=\"{}\">", anchor);
+        let _ = writeln!(sections, "<h2>{}</h2>", escape_html(&recipe.name));
+        sections.push_str("<ul>\n");
+        for ingredient in &recipe.ingredients {
+            let _ = writeln!(
+                sections,
+                "<li><span class=\"weight\">{:.1} g</span> {}</li>",
+                ingredient.grams,
+                escape_html(&ingredient.ingredient.name)
             );
         }
+        sections.push_str("</ul>\n");
+        sections.push_str("</section>\n");
+    }
+
+    fill_template(template, |name| match name {
+        "title" => Some(title),
+        "style" => Some(STYLE),
+        "contents" => Some(&contents),
+        "recipes" => Some(&sections),
+        _ => None,
+    })
+}
 
-        println!("</ul>");
-        println!();
+/// Replace each `{{name}}` in one pass, so recipe text that looks like a
+/// placeholder is left alone
+fn fill_template<'a>(template: &str, value: impl Fn(&str) -> Option<&'a str>) -> String {
+    let mut html = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find("{{") {
+        let Some(length) = rest[start..].find("}}") else {
+            break;
+        };
+        let placeholder = &rest[start..start + length + 2];
+        html.push_str(&rest[..start]);
+        html.push_str(value(placeholder[2..length].trim()).unwrap_or(placeholder));
+        rest = &rest[start + length + 2..];
     }
+    html.push_str(rest);
+    html
+}
 
-    // Add bottom padding so users can scroll any recipe to the top of their screen
-    // This is especially important for recipes near the end of the list
-    println!("<div style=\"height: 50vh;\"></div>");
-    println!();
-    println!("</body>");
-    println!("</html>");
+/// A unique `id` for each recipe, derived from its name, e.g. "mac-cheese" for "Mac & Cheese"
+fn anchors(recipes: &[Recipe]) -> Vec<String> {
+    let mut used = HashSet::new();
+    recipes
+        .iter()
+        .map(|recipe| {
+            let words: Vec<String> = recipe
+                .name
+                .split(|c: char| !c.is_alphanumeric())
+                .filter(|word| !word.is_empty())
+                .map(str::to_lowercase)
+                .collect();
+            let base = if words.is_empty() {
+                "recipe".to_string()
+            } else {
+                words.join("-")
+            };
+            let mut anchor = base.clone();
+            let mut number = 2;
+            while !used.insert(anchor.clone()) {
+                anchor = format!("{}-{}", base, number);
+                number += 1;
+            }
+            anchor
+        })
+        .collect()
+}
 
-    Ok(())
+fn render_error_html(error: &AppError) -> String {
+    format!(
+        "<!DOCTYPE html>\n<html>\n<head><title>Kitchen Reference</title></head>\n<body>\n\
+         <h1>Kitchen Reference</h1>\n<p>The catalog could not be loaded:</p>\n<pre>{}</pre>\n\
+         </body>\n</html>\n",
+        escape_html(&error.to_string())
+    )
 }
@@ -0,0 +1,202 @@
+use super::display::{format_calories, format_number_with_unit, render_table, truncate_name};
+use crate::catalog;
+use crate::catalog::items::{Nutrition, Recipe};
+use crate::error::AppResult;
+use clap::{Args, ValueEnum};
+use serde::Serialize;
+use std::io::{self, Write};
+use tabled::Tabled;
+
+#[derive(Args)]
+pub struct ListArgs {
+    #[arg(long, value_enum, default_value_t = SortKey::Name, help = "Sort order")]
+    sort: SortKey,
+    #[arg(long, help = "Reverse the sort order")]
+    reverse: bool,
+    #[arg(long, help = "Only recipes with this tag (repeatable)")]
+    tag: Vec<String>,
+    #[arg(
+        long,
+        value_name = "INGREDIENT_ID",
+        help = "Only recipes using this ingredient (repeatable)"
+    )]
+    contains: Vec<String>,
+    #[arg(long, value_name = "KCAL", help = "Minimum total calories")]
+    min_kcal: Option<f64>,
+    #[arg(long, value_name = "KCAL", help = "Maximum total calories")]
+    max_kcal: Option<f64>,
+    #[arg(long, value_name = "GRAMS", help = "Minimum total protein")]
+    min_protein: Option<f64>,
+    #[arg(long, value_name = "GRAMS", help = "Maximum total protein")]
+    max_protein: Option<f64>,
+    #[arg(long, value_name = "GRAMS", help = "Minimum total net carbs")]
+    min_carbs: Option<f64>,
+    #[arg(long, value_name = "GRAMS", help = "Maximum total net carbs")]
+    max_carbs: Option<f64>,
+    #[arg(long, value_name = "GRAMS", help = "Minimum total fat")]
+    min_fat: Option<f64>,
+    #[arg(long, value_name = "GRAMS", help = "Maximum total fat")]
+    max_fat: Option<f64>,
+    #[arg(long, value_enum, default_value_t = ListFormat::Table, help = "Output format")]
+    format: ListFormat,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum SortKey {
+    Name,
+    Kcal,
+    Protein,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum ListFormat {
+    Table,
+    Plain,
+    Json,
+}
+
+#[derive(Tabled)]
+struct ListRow {
+    #[tabled(rename = "Name")]
+    name: String,
+    #[tabled(rename = "Weight")]
+    weight: String,
+    #[tabled(rename = "Net carbs")]
+    carbs: String,
+    #[tabled(rename = "Protein")]
+    protein: String,
+    #[tabled(rename = "Calories")]
+    calories: String,
+}
+
+#[derive(Serialize)]
+pub(crate) struct ListEntry<'a> {
+    name: &'a str,
+    tags: &'a [String],
+    weight_grams: f64,
+    calories: f64,
+    protein_grams: f64,
+    net_carbs_grams: f64,
+    fat_grams: f64,
+    fiber_grams: f64,
+    /// Weight of the finished dish, if it differs from the ingredient weights
+    cooked_grams: Option<f64>,
+    /// Values per 100 g of the finished dish
+    per_100g: Option<Per100gEntry>,
+}
+
+#[derive(Serialize)]
+struct Per100gEntry {
+    calories: f64,
+    protein_grams: f64,
+    net_carbs_grams: f64,
+    fat_grams: f64,
+    fiber_grams: f64,
+}
+
+impl<'a> ListEntry<'a> {
+    pub(crate) fn new(recipe: &'a Recipe, totals: &Nutrition) -> Self {
+        ListEntry {
+            name: &recipe.name,
+            tags: &recipe.tags,
+            weight_grams: totals.grams,
+            calories: totals.calories,
+            protein_grams: totals.protein,
+            net_carbs_grams: totals.carbs,
+            fat_grams: totals.fat,
+            fiber_grams: totals.fiber,
+            cooked_grams: recipe.cooked_grams,
+            per_100g: recipe.per_100g().map(|per_100g| Per100gEntry {
+                calories: per_100g.calories,
+                protein_grams: per_100g.protein,
+                net_carbs_grams: per_100g.carbs,
+                fat_grams: per_100g.fat,
+                fiber_grams: per_100g.fiber,
+            }),
+        }
+    }
+}
+
+pub fn run(args: &ListArgs) -> AppResult<()> {
+    let recipes = catalog::load_recipes()?;
+
+    let mut entries: Vec<(&Recipe, Nutrition)> = recipes
+        .iter()
+        .map(|recipe| (recipe, recipe.totals()))
+        .filter(|(recipe, totals)| matches_filters(args, recipe, totals))
+        .collect();
+
+    match args.sort {
+        SortKey::Name => entries.sort_by_key(|a| a.0.name.to_lowercase()),
+        SortKey::Kcal => entries.sort_by(|a, b| a.1.calories.total_cmp(&b.1.calories)),
+        SortKey::Protein => entries.sort_by(|a, b| a.1.protein.total_cmp(&b.1.protein)),
+    }
+    if args.reverse {
+        entries.reverse();
+    }
+
+    let mut stdout = io::stdout();
+    match args.format {
+        ListFormat::Table => write_table(&entries, &mut stdout)?,
+        ListFormat::Plain => write_plain(&entries, &mut stdout)?,
+        ListFormat::Json => write_json(&entries, &mut stdout)?,
+    }
+    Ok(())
+}
+
+fn matches_filters(args: &ListArgs, recipe: &Recipe, totals: &Nutrition) -> bool {
+    let within = |value: f64, min: Option<f64>, max: Option<f64>| {
+        min.is_none_or(|min| value >= min) && max.is_none_or(|max| value <= max)
+    };
+
+    args.tag.iter().all(|tag| recipe.has_tag(tag))
+        && args.contains.iter().all(|id| recipe.uses_ingredient(id))
+        && within(totals.calories, args.min_kcal, args.max_kcal)
+        && within(totals.protein, args.min_protein, args.max_protein)
+        && within(totals.carbs, args.min_carbs, args.max_carbs)
+        && within(totals.fat, args.min_fat, args.max_fat)
+}
+
+fn write_table<W: Write>(entries: &[(&Recipe, Nutrition)], writer: &mut W) -> io::Result<()> {
+    if entries.is_empty() {
+        return writeln!(writer, "No recipes match the given filters.");
+    }
+
+    let rows: Vec<ListRow> = entries
+        .iter()
+        .map(|(recipe, totals)| ListRow {
+            name: truncate_name(&recipe.name),
+            weight: format_number_with_unit(totals.grams, "g"),
+            carbs: format_number_with_unit(totals.carbs, "g"),
+            protein: format_number_with_unit(totals.protein, "g"),
+            calories: format_calories(totals.calories),
+        })
+        .collect();
+
+    render_table(&rows, writer)
+}
+
+fn write_plain<W: Write>(entries: &[(&Recipe, Nutrition)], writer: &mut W) -> io::Result<()> {
+    for (recipe, totals) in entries {
+        writeln!(
+            writer,
+            "{}: {}, {}, {} protein, {} net carbs",
+            recipe.name,
+            format_number_with_unit(totals.grams, "g"),
+            format_calories(totals.calories),
+            format_number_with_unit(totals.protein, "g"),
+            format_number_with_unit(totals.carbs, "g"),
+        )?;
+    }
+    Ok(())
+}
+
+fn write_json<W: Write>(entries: &[(&Recipe, Nutrition)], writer: &mut W) -> io::Result<()> {
+    let json_entries: Vec<ListEntry> = entries
+        .iter()
+        .map(|(recipe, totals)| ListEntry::new(recipe, totals))
+        .collect();
+
+    serde_json::to_writer_pretty(&mut *writer, &json_entries)?;
+    writeln!(writer)
+}
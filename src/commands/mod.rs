@@ -1,3 +1,19 @@
+pub mod compare;
+pub mod completions;
+pub mod diary;
+mod diff;
+mod display;
+pub mod fmt;
+pub mod ingredient;
 pub mod init;
 pub mod kitchen_ref;
+pub mod list;
+pub mod optimize;
+pub mod pantry;
+pub mod plan;
 pub mod recipe;
+pub mod serve;
+pub mod shell;
+pub mod shop;
+pub mod tui;
+mod watch;
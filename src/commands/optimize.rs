@@ -0,0 +1,332 @@
+use super::display::{
+    TableOptions, format_difference, format_number_with_unit, render_nutrition_table, render_table,
+    truncate_name,
+};
+use super::recipe::parse_ingredient_amount;
+use crate::catalog;
+use crate::catalog::items::{Ingredient, Nutrition, Recipe, WeightedIngredient};
+use crate::error::{AppError, AppResult};
+use crate::search::resolve_recipe;
+use crate::utils::least_change::{self, LeastChangeProblem, LinearConstraint};
+use clap::{ArgGroup, Args};
+use std::io::{self, Write};
+use tabled::Tabled;
+
+#[derive(Args)]
+#[command(group(ArgGroup::new("targets").required(true).multiple(true)))]
+pub struct OptimizeArgs {
+    #[arg(add = super::completions::recipe_names(), help = "Recipe name or search terms")]
+    recipe: String,
+    #[arg(
+        long,
+        value_name = "KCAL",
+        group = "targets",
+        conflicts_with_all = ["min_kcal", "max_kcal"],
+        help = "Exact total calories"
+    )]
+    kcal: Option<f64>,
+    #[arg(
+        long,
+        value_name = "KCAL",
+        group = "targets",
+        help = "Minimum total calories"
+    )]
+    min_kcal: Option<f64>,
+    #[arg(
+        long,
+        value_name = "KCAL",
+        group = "targets",
+        help = "Maximum total calories"
+    )]
+    max_kcal: Option<f64>,
+    #[arg(
+        long,
+        value_name = "GRAMS",
+        group = "targets",
+        conflicts_with_all = ["min_protein", "max_protein"],
+        help = "Exact total protein"
+    )]
+    protein: Option<f64>,
+    #[arg(
+        long,
+        value_name = "GRAMS",
+        group = "targets",
+        help = "Minimum total protein"
+    )]
+    min_protein: Option<f64>,
+    #[arg(
+        long,
+        value_name = "GRAMS",
+        group = "targets",
+        help = "Maximum total protein"
+    )]
+    max_protein: Option<f64>,
+    #[arg(
+        long,
+        value_name = "GRAMS",
+        group = "targets",
+        conflicts_with_all = ["min_carbs", "max_carbs"],
+        help = "Exact total net carbs"
+    )]
+    carbs: Option<f64>,
+    #[arg(
+        long,
+        value_name = "GRAMS",
+        group = "targets",
+        help = "Minimum total net carbs"
+    )]
+    min_carbs: Option<f64>,
+    #[arg(
+        long,
+        value_name = "GRAMS",
+        group = "targets",
+        help = "Maximum total net carbs"
+    )]
+    max_carbs: Option<f64>,
+    #[arg(
+        long,
+        value_name = "GRAMS",
+        group = "targets",
+        conflicts_with_all = ["min_fat", "max_fat"],
+        help = "Exact total fat"
+    )]
+    fat: Option<f64>,
+    #[arg(
+        long,
+        value_name = "GRAMS",
+        group = "targets",
+        help = "Minimum total fat"
+    )]
+    min_fat: Option<f64>,
+    #[arg(
+        long,
+        value_name = "GRAMS",
+        group = "targets",
+        help = "Maximum total fat"
+    )]
+    max_fat: Option<f64>,
+    #[arg(
+        long = "min",
+        value_name = "ID=GRAMS",
+        value_parser = parse_ingredient_amount,
+        add = super::completions::ingredient_amounts(),
+        help = "Lowest allowed amount for an ingredient (repeatable)"
+    )]
+    min_amounts: Vec<(String, f64)>,
+    #[arg(
+        long = "max",
+        value_name = "ID=GRAMS",
+        value_parser = parse_ingredient_amount,
+        add = super::completions::ingredient_amounts(),
+        help = "Highest allowed amount for an ingredient (repeatable)"
+    )]
+    max_amounts: Vec<(String, f64)>,
+    #[arg(
+        long,
+        value_name = "ID",
+        add = super::completions::ingredient_ids(),
+        help = "Keep an ingredient at its current amount (repeatable)"
+    )]
+    lock: Vec<String>,
+}
+
+/// Limits on one nutrition value of the whole recipe
+struct Target {
+    /// Text after an amount, e.g. " g protein"
+    suffix: &'static str,
+    value: fn(&Nutrition) -> f64,
+    min: Option<f64>,
+    max: Option<f64>,
+}
+
+impl Target {
+    fn describe(&self) -> String {
+        let amount = |v: f64| format!("{}{}", v, self.suffix);
+        match (self.min, self.max) {
+            (Some(min), Some(max)) if min == max => amount(min),
+            (Some(min), Some(max)) => format!("{} to {}", amount(min), amount(max)),
+            (Some(min), None) => format!("at least {}", amount(min)),
+            (None, Some(max)) => format!("at most {}", amount(max)),
+            (None, None) => String::new(),
+        }
+    }
+}
+
+impl OptimizeArgs {
+    fn targets(&self) -> Vec<Target> {
+        let target = |suffix, value, exact: Option<f64>, min, max| Target {
+            suffix,
+            value,
+            min: exact.or(min),
+            max: exact.or(max),
+        };
+        [
+            target(
+                " g protein",
+                |n: &Nutrition| n.protein,
+                self.protein,
+                self.min_protein,
+                self.max_protein,
+            ),
+            target(
+                " kcal",
+                |n: &Nutrition| n.calories,
+                self.kcal,
+                self.min_kcal,
+                self.max_kcal,
+            ),
+            target(
+                " g net carbs",
+                |n: &Nutrition| n.carbs,
+                self.carbs,
+                self.min_carbs,
+                self.max_carbs,
+            ),
+            target(
+                " g fat",
+                |n: &Nutrition| n.fat,
+                self.fat,
+                self.min_fat,
+                self.max_fat,
+            ),
+        ]
+        .into_iter()
+        .filter(|t| t.min.is_some() || t.max.is_some())
+        .collect()
+    }
+}
+
+#[derive(Tabled)]
+struct ChangeRow {
+    #[tabled(rename = "Name")]
+    name: String,
+    #[tabled(rename = "Before")]
+    before: String,
+    #[tabled(rename = "After")]
+    after: String,
+    #[tabled(rename = "Change")]
+    change: String,
+}
+
+pub fn run(args: &OptimizeArgs) -> AppResult<()> {
+    let recipes = catalog::load_recipes()?;
+    let recipe = resolve_recipe(&recipes, &args.recipe)?;
+
+    let ingredients = merge_ingredients(recipe);
+    let targets = args.targets();
+    let problem = build_problem(recipe, &ingredients, &targets, args)?;
+    let solution = least_change::solve(&problem).ok_or_else(|| AppError::OptimizationFailed {
+        message: format!(
+            "No amounts for '{}' meet all targets within the ingredient limits\n\nTip: Relax a target, widen --min/--max limits or unlock ingredients.",
+            recipe.name
+        ),
+    })?;
+
+    let adjusted: Vec<WeightedIngredient> = ingredients
+        .iter()
+        .zip(&solution)
+        .map(|(ingredient, grams)| WeightedIngredient {
+            grams: *grams,
+            ingredient: ingredient.ingredient.clone(),
+        })
+        .collect();
+
+    let mut stdout = io::stdout();
+    writeln!(stdout, "Recipe: {}", recipe.name)?;
+    writeln!(
+        stdout,
+        "Targets: {}",
+        targets
+            .iter()
+            .map(Target::describe)
+            .collect::<Vec<_>>()
+            .join(", ")
+    )?;
+    writeln!(stdout)?;
+
+    let changes: Vec<ChangeRow> = ingredients
+        .iter()
+        .zip(&adjusted)
+        .map(|(before, after)| ChangeRow {
+            name: truncate_name(&before.ingredient.name),
+            before: format_number_with_unit(before.grams, "g"),
+            after: format_number_with_unit(after.grams, "g"),
+            change: format_difference(before.grams, after.grams, |v| {
+                format_number_with_unit(v, "g")
+            }),
+        })
+        .collect();
+    render_table(&changes, &mut stdout)?;
+    writeln!(stdout)?;
+
+    render_nutrition_table(&adjusted, &TableOptions::default(), &mut stdout)?;
+    Ok(())
+}
+
+/// One entry per ingredient, with repeated entries added together
+fn merge_ingredients(recipe: &Recipe) -> Vec<WeightedIngredient> {
+    let mut merged: Vec<WeightedIngredient> = Vec::new();
+    for entry in &recipe.ingredients {
+        match merged
+            .iter_mut()
+            .find(|m| m.ingredient.id == entry.ingredient.id)
+        {
+            Some(existing) => existing.grams += entry.grams,
+            None => merged.push(entry.clone()),
+        }
+    }
+    merged
+}
+
+fn build_problem(
+    recipe: &Recipe,
+    ingredients: &[WeightedIngredient],
+    targets: &[Target],
+    args: &OptimizeArgs,
+) -> AppResult<LeastChangeProblem> {
+    let mut bounds: Vec<(f64, f64)> = vec![(0.0, f64::INFINITY); ingredients.len()];
+
+    let index_of = |id: &str| -> AppResult<usize> {
+        ingredients
+            .iter()
+            .position(|i| i.ingredient.id == id)
+            .ok_or_else(|| AppError::OptimizationFailed {
+                message: format!("Recipe '{}' does not use ingredient '{}'", recipe.name, id),
+            })
+    };
+    for (id, grams) in &args.min_amounts {
+        let bound = &mut bounds[index_of(id)?].0;
+        *bound = bound.max(*grams);
+    }
+    for (id, grams) in &args.max_amounts {
+        let bound = &mut bounds[index_of(id)?].1;
+        *bound = bound.min(*grams);
+    }
+    for id in &args.lock {
+        let index = index_of(id)?;
+        bounds[index] = (ingredients[index].grams, ingredients[index].grams);
+    }
+
+    let per_gram: Vec<Nutrition> = ingredients
+        .iter()
+        .map(|i| nutrition_per_gram(&i.ingredient))
+        .collect();
+    let constraints = targets
+        .iter()
+        .map(|target| LinearConstraint {
+            coefficients: per_gram.iter().map(|n| (target.value)(n)).collect(),
+            min: target.min.unwrap_or(f64::NEG_INFINITY),
+            max: target.max.unwrap_or(f64::INFINITY),
+        })
+        .collect();
+
+    Ok(LeastChangeProblem {
+        start: ingredients.iter().map(|i| i.grams).collect(),
+        bounds,
+        constraints,
+    })
+}
+
+fn nutrition_per_gram(ingredient: &Ingredient) -> Nutrition {
+    ingredient.nutrition_for(1.0)
+}
@@ -0,0 +1,253 @@
+use super::display::{apply_standard_style, format_number_with_unit};
+use super::recipe::parse_factor;
+use crate::catalog::items::Pantry;
+use crate::catalog::{self, Catalog, StockChange};
+use crate::error::{AppError, AppResult};
+use crate::search::ingredients::resolve_ingredient;
+use crate::search::resolve_recipe;
+use clap::{Args, Subcommand};
+use std::io::{self, Write};
+use tabled::builder::Builder;
+use tabled::settings::{Color, Modify, object::Rows};
+
+#[derive(Subcommand)]
+pub enum PantryCommand {
+    #[command(about = "Add stock of an ingredient to pantry.jsonc")]
+    Add {
+        #[arg(
+            add = super::completions::ingredient_ids(),
+            help = "Ingredient ID, name or search terms"
+        )]
+        ingredient: String,
+        #[arg(value_parser = parse_grams, help = "Amount to add (e.g., 500 or 500g)")]
+        grams: f64,
+        #[arg(
+            long,
+            value_name = "GRAMS",
+            value_parser = parse_grams,
+            help = "Warn when stock is at or below this amount"
+        )]
+        low: Option<f64>,
+    },
+}
+
+#[derive(Args)]
+pub struct CookArgs {
+    #[arg(add = super::completions::recipe_names(), help = "Recipe name or search terms")]
+    recipe: String,
+    #[arg(
+        long,
+        value_parser = parse_factor,
+        default_value_t = 1.0,
+        help = "Multiple of the recipe cooked (e.g., 1.5)"
+    )]
+    factor: f64,
+    #[arg(long, help = "Cook even when stock is short, using up what is there")]
+    force: bool,
+}
+
+pub fn run(command: Option<&PantryCommand>) -> AppResult<()> {
+    match command {
+        Some(PantryCommand::Add {
+            ingredient,
+            grams,
+            low,
+        }) => add(ingredient, *grams, *low),
+        None => show(),
+    }
+}
+
+pub fn cook(args: &CookArgs) -> AppResult<()> {
+    let catalog = catalog::load()?;
+    let pantry = catalog::load_pantry()?;
+    let recipe = resolve_recipe(&catalog.recipes, &args.recipe)?.scaled(args.factor);
+
+    let shortfalls = pantry.shortfalls(&recipe.ingredients);
+    if !shortfalls.is_empty() && !args.force {
+        return Err(AppError::InsufficientStock {
+            recipe: recipe.name.clone(),
+            shortfalls: describe_shortfalls(&catalog, &shortfalls),
+        });
+    }
+
+    let changes: Vec<StockChange> = recipe
+        .ingredients
+        .iter()
+        .map(|entry| StockChange {
+            id: entry.ingredient.id.clone(),
+            grams: -entry.grams,
+            low_grams: None,
+        })
+        .collect();
+    catalog::update_pantry(&changes)?;
+
+    match args.factor {
+        1.0 => println!("✅ Cooked '{}' and updated the pantry", recipe.name),
+        factor => println!(
+            "✅ Cooked '{}' (×{:.2}) and updated the pantry",
+            recipe.name, factor
+        ),
+    }
+    let pantry = catalog::load_pantry()?;
+    let used: Vec<&str> = changes.iter().map(|c| c.id.as_str()).collect();
+    let low: Vec<String> = pantry
+        .items
+        .iter()
+        .filter(|item| item.is_low() && used.contains(&item.id.as_str()))
+        .map(|item| {
+            format!(
+                "{} ({} left)",
+                ingredient_name(&catalog, &item.id),
+                format_number_with_unit(item.grams, "g")
+            )
+        })
+        .collect();
+    if !low.is_empty() {
+        println!("⚠️  Running low: {}", low.join(", "));
+    }
+    Ok(())
+}
+
+fn add(query: &str, grams: f64, low: Option<f64>) -> AppResult<()> {
+    let catalog = catalog::load()?;
+    let ingredient = resolve_ingredient(&catalog.ingredients, query)?;
+    catalog::update_pantry(&[StockChange {
+        id: ingredient.id.clone(),
+        grams,
+        low_grams: low,
+    }])?;
+
+    let stock = catalog::load_pantry()?.stock(&ingredient.id);
+    println!(
+        "✅ Added {} of '{}' to the pantry ({} in stock)",
+        format_number_with_unit(grams, "g"),
+        ingredient.id,
+        format_number_with_unit(stock, "g")
+    );
+    Ok(())
+}
+
+fn show() -> AppResult<()> {
+    let catalog = catalog::load()?;
+    let pantry = catalog::load_pantry()?;
+    let mut stdout = io::stdout();
+
+    writeln!(stdout, "Pantry")?;
+    writeln!(stdout)?;
+    if pantry.items.is_empty() {
+        writeln!(
+            stdout,
+            "The pantry is empty. Add stock with 'nutriterm pantry add <ingredient> <grams>'."
+        )?;
+        return Ok(());
+    }
+
+    render_stock_table(&catalog, &pantry, &mut stdout)?;
+
+    let low: Vec<&str> = pantry
+        .items
+        .iter()
+        .filter(|item| item.is_low())
+        .map(|item| ingredient_name(&catalog, &item.id))
+        .collect();
+    if !low.is_empty() {
+        writeln!(stdout, "⚠️  Running low: {}", low.join(", "))?;
+    }
+
+    let (cookable, short): (Vec<_>, Vec<_>) = catalog
+        .recipes
+        .iter()
+        .map(|recipe| (recipe, pantry.shortfalls(&recipe.ingredients)))
+        .partition(|(_, shortfalls)| shortfalls.is_empty());
+
+    writeln!(stdout)?;
+    writeln!(stdout, "Cookable from current stock:")?;
+    if cookable.is_empty() {
+        writeln!(stdout, "  (none)")?;
+    }
+    for (recipe, _) in &cookable {
+        let batches = pantry.batches(&recipe.ingredients);
+        let noun = if batches == 1 { "batch" } else { "batches" };
+        writeln!(stdout, "  ✓ {} ({} {})", recipe.name, batches, noun)?;
+    }
+
+    if !short.is_empty() {
+        writeln!(stdout)?;
+        writeln!(stdout, "Not cookable:")?;
+        for (recipe, shortfalls) in &short {
+            writeln!(
+                stdout,
+                "  ✗ {}: short {}",
+                recipe.name,
+                describe_shortfalls(&catalog, shortfalls).join(", ")
+            )?;
+        }
+    }
+    Ok(())
+}
+
+/// Stock per ingredient; running low in yellow, empty in red
+fn render_stock_table<W: Write>(
+    catalog: &Catalog,
+    pantry: &Pantry,
+    writer: &mut W,
+) -> io::Result<()> {
+    let mut builder = Builder::default();
+    builder.push_record(["Ingredient", "Stock", "Low at"]);
+    for item in &pantry.items {
+        builder.push_record([
+            ingredient_name(catalog, &item.id).to_string(),
+            format_number_with_unit(item.grams, "g"),
+            item.low_grams
+                .map(|low| format_number_with_unit(low, "g"))
+                .unwrap_or_default(),
+        ]);
+    }
+
+    let mut table = builder.build();
+    apply_standard_style(&mut table);
+    for (index, item) in pantry.items.iter().enumerate() {
+        // Row 0 is the header
+        let row = Rows::new(index + 1..=index + 1);
+        if item.grams <= 0.0 {
+            table.with(Modify::new(row).with(Color::FG_RED));
+        } else if item.is_low() {
+            table.with(Modify::new(row).with(Color::FG_YELLOW));
+        }
+    }
+    writeln!(writer, "{}", table)
+}
+
+/// e.g. "30.0 g Brown Rice (cooked)"
+fn describe_shortfalls(catalog: &Catalog, shortfalls: &[(String, f64)]) -> Vec<String> {
+    shortfalls
+        .iter()
+        .map(|(id, missing)| {
+            format!(
+                "{} {}",
+                format_number_with_unit(*missing, "g"),
+                ingredient_name(catalog, id)
+            )
+        })
+        .collect()
+}
+
+fn ingredient_name<'a>(catalog: &'a Catalog, id: &'a str) -> &'a str {
+    catalog
+        .ingredients
+        .iter()
+        .find(|i| i.id == id)
+        .map_or(id, |i| i.name.as_str())
+}
+
+fn parse_grams(value: &str) -> Result<f64, String> {
+    let normalized = value.trim().to_lowercase();
+    normalized
+        .strip_suffix('g')
+        .unwrap_or(&normalized)
+        .trim()
+        .parse::<f64>()
+        .ok()
+        .filter(|grams| grams.is_finite() && *grams >= 0.0)
+        .ok_or_else(|| format!("expected grams like 500 or 500g, got '{}'", value))
+}
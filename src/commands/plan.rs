@@ -0,0 +1,75 @@
+use super::display::{
+    Summary, TableOptions, render_nutrition_table, render_summary_table, render_target_progress,
+};
+use crate::catalog;
+use crate::catalog::items::{DayPlan, PlannedMeal, Portion};
+use crate::error::{AppError, AppResult};
+use std::io::{self, Write};
+
+pub fn run(day_query: &str, person: Option<&str>) -> AppResult<()> {
+    let days = catalog::load_plans()?;
+    let day = resolve_day(&days, day_query)?;
+    let targets = catalog::load_targets(person)?;
+
+    let mut stdout = io::stdout();
+    writeln!(stdout, "Plan: {}", day.name)?;
+    writeln!(stdout)?;
+
+    for meal in &day.meals {
+        writeln!(stdout, "{}", meal_heading(meal))?;
+        render_nutrition_table(&meal.ingredients(), &TableOptions::default(), &mut stdout)?;
+        writeln!(stdout)?;
+    }
+
+    writeln!(stdout, "Day total")?;
+    let rows: Vec<_> = day
+        .meals
+        .iter()
+        .map(|meal| (meal.meal.clone(), meal.nutrition()))
+        .collect();
+    render_summary_table(&rows, Summary::Total, &mut stdout)?;
+
+    if let Some(targets) = &targets {
+        writeln!(stdout)?;
+        writeln!(stdout, "Daily targets")?;
+        render_target_progress(&day.totals(), targets, &mut stdout)?;
+    }
+    Ok(())
+}
+
+/// Exact name (ignoring case), otherwise the only day containing the query
+fn resolve_day<'a>(days: &'a [DayPlan], query: &str) -> AppResult<&'a DayPlan> {
+    if let Some(day) = days.iter().find(|d| d.name.eq_ignore_ascii_case(query)) {
+        return Ok(day);
+    }
+
+    let query_lower = query.to_lowercase();
+    let matches: Vec<&DayPlan> = days
+        .iter()
+        .filter(|d| d.name.to_lowercase().contains(&query_lower))
+        .collect();
+    match matches.as_slice() {
+        [day] => Ok(day),
+        _ => {
+            let candidates = if matches.is_empty() {
+                days.iter().collect()
+            } else {
+                matches
+            };
+            Err(AppError::DayNotFound {
+                query: query.to_string(),
+                candidates: candidates.iter().map(|d| d.name.clone()).collect(),
+            })
+        }
+    }
+}
+
+/// e.g. "Dinner: Chicken Rice Bowl (×1.50)"
+fn meal_heading(meal: &PlannedMeal) -> String {
+    let portion = match meal.portion {
+        Portion::Multiplier(1.0) => String::new(),
+        Portion::Multiplier(multiplier) => format!(" (×{:.2})", multiplier),
+        Portion::Grams(grams) => format!(" ({} g, ×{:.2})", grams, meal.factor()),
+    };
+    format!("{}: {}{}", meal.meal, meal.recipe.name, portion)
+}
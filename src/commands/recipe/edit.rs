@@ -0,0 +1,106 @@
+use crate::catalog::{self, Edit};
+use crate::commands::completions::{ingredient_amounts, ingredient_ids, recipe_names};
+use crate::error::AppResult;
+use crate::search::resolve_recipe;
+use clap::Subcommand;
+
+#[derive(Subcommand)]
+pub enum RecipeCommand {
+    #[command(about = "Add a new recipe to recipes.jsonc")]
+    Add {
+        #[arg(help = "Recipe name")]
+        name: String,
+        #[arg(long, help = "Tag for the recipe (repeatable)")]
+        tag: Vec<String>,
+        #[arg(
+            long,
+            value_name = "ID=GRAMS",
+            value_parser = parse_ingredient_amount,
+            add = ingredient_amounts(),
+            help = "Ingredient with its amount, e.g. brown_rice=80 (repeatable)"
+        )]
+        ingredient: Vec<(String, f64)>,
+    },
+    #[command(about = "Set an ingredient's amount in a recipe, adding it if missing")]
+    Set {
+        #[arg(add = recipe_names(), help = "Recipe name or search terms")]
+        recipe: String,
+        #[arg(add = ingredient_ids(), help = "Ingredient ID")]
+        ingredient_id: String,
+        #[arg(help = "Amount in grams")]
+        grams: f64,
+    },
+    #[command(about = "Remove an ingredient from a recipe")]
+    RemoveIngredient {
+        #[arg(add = recipe_names(), help = "Recipe name or search terms")]
+        recipe: String,
+        #[arg(add = ingredient_ids(), help = "Ingredient ID")]
+        ingredient_id: String,
+    },
+}
+
+pub fn run(command: &RecipeCommand) -> AppResult<()> {
+    match command {
+        RecipeCommand::Add {
+            name,
+            tag,
+            ingredient,
+        } => {
+            apply(Edit::AddRecipe {
+                name: name.clone(),
+                tags: tag.clone(),
+                ingredients: ingredient.clone(),
+            })?;
+            println!("✅ Added recipe '{}' to recipes.jsonc", name);
+        }
+        RecipeCommand::Set {
+            recipe,
+            ingredient_id,
+            grams,
+        } => {
+            let recipe_name = resolve_recipe_name(recipe)?;
+            apply(Edit::SetRecipeIngredient {
+                recipe: recipe_name.clone(),
+                ingredient_id: ingredient_id.clone(),
+                grams: *grams,
+            })?;
+            println!(
+                "✅ Set '{}' to {} g in '{}'",
+                ingredient_id, grams, recipe_name
+            );
+        }
+        RecipeCommand::RemoveIngredient {
+            recipe,
+            ingredient_id,
+        } => {
+            let recipe_name = resolve_recipe_name(recipe)?;
+            apply(Edit::RemoveRecipeIngredient {
+                recipe: recipe_name.clone(),
+                ingredient_id: ingredient_id.clone(),
+            })?;
+            println!("✅ Removed '{}' from '{}'", ingredient_id, recipe_name);
+        }
+    }
+    Ok(())
+}
+
+fn resolve_recipe_name(query: &str) -> AppResult<String> {
+    let recipes = catalog::load_recipes()?;
+    Ok(resolve_recipe(&recipes, query)?.name.clone())
+}
+
+fn apply(edit: Edit) -> AppResult<()> {
+    let changes = catalog::plan_edit(&edit)?;
+    catalog::write_changes(&changes)
+}
+
+pub(crate) fn parse_ingredient_amount(value: &str) -> Result<(String, f64), String> {
+    let (id, grams) = value
+        .split_once('=')
+        .ok_or_else(|| format!("expected ID=GRAMS, got '{}'", value))?;
+    let grams = grams
+        .trim()
+        .parse()
+        .map_err(|_| format!("invalid gram amount '{}'", grams))?;
+    Ok((id.trim().to_string(), grams))
+}
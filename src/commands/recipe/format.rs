@@ -0,0 +1,219 @@
+use super::super::display::{
+    format_calories, format_number_with_unit, nutrition_cells, nutrition_headers,
+};
+use crate::catalog::items::{Nutrition, Recipe};
+use clap::ValueEnum;
+use serde::Serialize;
+use std::io::{self, Write};
+
+#[derive(Clone, Copy, Default, ValueEnum)]
+pub enum RecipeFormat {
+    /// Colored table for the terminal
+    #[default]
+    Table,
+    /// One line per ingredient
+    Plain,
+    /// Table to paste into a wiki or README
+    Markdown,
+    /// Unrounded values for spreadsheets
+    Csv,
+    /// Unrounded values for scripts
+    Json,
+}
+
+impl RecipeFormat {
+    /// Whether the output is meant for other programs, which need a failing exit code
+    /// rather than a message when no single recipe is found
+    pub(crate) fn is_machine_readable(self) -> bool {
+        matches!(self, RecipeFormat::Csv | RecipeFormat::Json)
+    }
+}
+
+/// Nutrition of a weighed amount, as machine-readable formats report it (unrounded)
+#[derive(Serialize)]
+pub(crate) struct NutritionEntry {
+    weight_grams: f64,
+    calories: f64,
+    protein_grams: f64,
+    net_carbs_grams: f64,
+    fat_grams: f64,
+    fiber_grams: f64,
+}
+
+impl From<Nutrition> for NutritionEntry {
+    fn from(nutrition: Nutrition) -> Self {
+        NutritionEntry {
+            weight_grams: nutrition.grams,
+            calories: nutrition.calories,
+            protein_grams: nutrition.protein,
+            net_carbs_grams: nutrition.carbs,
+            fat_grams: nutrition.fat,
+            fiber_grams: nutrition.fiber,
+        }
+    }
+}
+
+/// A recipe's per-ingredient and total nutrition
+#[derive(Serialize)]
+pub(crate) struct RecipeNutrition<'a> {
+    name: &'a str,
+    factor: f64,
+    ingredients: Vec<RecipeIngredient<'a>>,
+    total: NutritionEntry,
+    /// Weight of the finished dish, if it differs from the ingredient weights
+    cooked_grams: Option<f64>,
+    /// Values per 100 g of the finished dish
+    per_100g: Option<NutritionEntry>,
+}
+
+#[derive(Serialize)]
+struct RecipeIngredient<'a> {
+    id: &'a str,
+    name: &'a str,
+    #[serde(flatten)]
+    nutrition: NutritionEntry,
+}
+
+impl<'a> RecipeNutrition<'a> {
+    /// `recipe` is already scaled by `factor`
+    pub(crate) fn new(recipe: &'a Recipe, factor: f64) -> Self {
+        RecipeNutrition {
+            name: &recipe.name,
+            factor,
+            ingredients: recipe
+                .ingredients
+                .iter()
+                .map(|i| RecipeIngredient {
+                    id: &i.ingredient.id,
+                    name: &i.ingredient.name,
+                    nutrition: i.nutrition().into(),
+                })
+                .collect(),
+            total: recipe.totals().into(),
+            cooked_grams: recipe.cooked_grams,
+            per_100g: recipe.per_100g().map(NutritionEntry::from),
+        }
+    }
+}
+
+/// Rows of a recipe's nutrition: each ingredient, the total and optionally per 100 g
+fn rows(recipe: &Recipe, per_100g: bool) -> Vec<(&str, Option<&str>, Nutrition)> {
+    let mut rows: Vec<_> = recipe
+        .ingredients
+        .iter()
+        .map(|i| {
+            (
+                i.ingredient.name.as_str(),
+                Some(i.ingredient.id.as_str()),
+                i.nutrition(),
+            )
+        })
+        .collect();
+    rows.push(("Total", None, recipe.totals()));
+    if per_100g && let Some(nutrition) = recipe.per_100g() {
+        rows.push(("Per 100 g", None, nutrition));
+    }
+    rows
+}
+
+fn title(recipe: &Recipe, factor: Option<f64>) -> String {
+    match factor {
+        Some(factor) => format!("{} (×{:.2})", recipe.name, factor),
+        None => recipe.name.clone(),
+    }
+}
+
+pub(super) fn write_plain<W: Write>(
+    recipe: &Recipe,
+    factor: Option<f64>,
+    per_100g: bool,
+    writer: &mut W,
+) -> io::Result<()> {
+    writeln!(writer, "{}", title(recipe, factor))?;
+    for (name, _, nutrition) in rows(recipe, per_100g) {
+        writeln!(
+            writer,
+            "{}: {}, {}, {} protein, {} net carbs, {} fat, {} fiber",
+            name,
+            format_number_with_unit(nutrition.grams, "g"),
+            format_calories(nutrition.calories),
+            format_number_with_unit(nutrition.protein, "g"),
+            format_number_with_unit(nutrition.carbs, "g"),
+            format_number_with_unit(nutrition.fat, "g"),
+            format_number_with_unit(nutrition.fiber, "g"),
+        )?;
+    }
+    Ok(())
+}
+
+pub(super) fn write_markdown<W: Write>(
+    recipe: &Recipe,
+    factor: Option<f64>,
+    per_100g: bool,
+    writer: &mut W,
+) -> io::Result<()> {
+    let cell = |text: &str| text.replace('|', "\\|");
+    let headers = nutrition_headers();
+
+    writeln!(writer, "## {}", cell(&title(recipe, factor)))?;
+    writeln!(writer)?;
+    writeln!(writer, "| {} |", headers.join(" | "))?;
+    let alignments: Vec<&str> = (0..headers.len())
+        .map(|column| if column == 0 { ":---" } else { "---:" })
+        .collect();
+    writeln!(writer, "| {} |", alignments.join(" | "))?;
+    for (name, id, nutrition) in rows(recipe, per_100g) {
+        let mut cells = nutrition_cells(&cell(name), &nutrition);
+        if id.is_none() {
+            for text in &mut cells {
+                *text = format!("**{}**", text);
+            }
+        }
+        writeln!(writer, "| {} |", cells.join(" | "))?;
+    }
+    Ok(())
+}
+
+pub(super) fn write_csv<W: Write>(
+    recipe: &Recipe,
+    per_100g: bool,
+    writer: &mut W,
+) -> io::Result<()> {
+    let field = |text: &str| {
+        if text.contains([',', '"', '\n']) {
+            format!("\"{}\"", text.replace('"', "\"\""))
+        } else {
+            text.to_string()
+        }
+    };
+
+    writeln!(
+        writer,
+        "name,id,weight_grams,net_carbs_grams,protein_grams,fat_grams,fiber_grams,calories"
+    )?;
+    for (name, id, nutrition) in rows(recipe, per_100g) {
+        writeln!(
+            writer,
+            "{},{},{},{},{},{},{},{}",
+            field(name),
+            field(id.unwrap_or("")),
+            nutrition.grams,
+            nutrition.carbs,
+            nutrition.protein,
+            nutrition.fat,
+            nutrition.fiber,
+            nutrition.calories
+        )?;
+    }
+    Ok(())
+}
+
+pub(super) fn write_json<W: Write>(
+    recipe: &Recipe,
+    factor: Option<f64>,
+    writer: &mut W,
+) -> io::Result<()> {
+    let entry = RecipeNutrition::new(recipe, factor.unwrap_or(1.0));
+    serde_json::to_writer_pretty(&mut *writer, &entry)?;
+    writeln!(writer)
+}
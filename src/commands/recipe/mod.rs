@@ -1,27 +1,98 @@
-mod display;
-mod search;
+mod edit;
+mod format;
+mod scale;
 
+pub(crate) use edit::parse_ingredient_amount;
+pub use edit::{RecipeCommand, run as run_edit};
+pub use format::RecipeFormat;
+pub(crate) use format::{NutritionEntry, RecipeNutrition};
+pub use scale::ScaleArgs;
+pub(crate) use scale::{parse_factor, parse_scale_target};
+
+use super::display::{TableOptions, render_nutrition_table};
+use super::watch;
 use crate::catalog;
-use crate::error::AppResult;
-use display::render_nutrition_table;
-use search::{find_exact_match, find_substring_matches, parse_search_terms};
-use std::io;
-
-pub fn run(recipe_name: &str) -> AppResult<()> {
-    let recipes = catalog::load_recipes()?;
-
-    if let Some(recipe) = find_exact_match(&recipes, recipe_name) {
-        println!("Recipe: {}", recipe.name);
-        println!();
-        let mut stdout = io::stdout();
-        render_nutrition_table(&recipe.ingredients, &mut stdout)?;
-        return Ok(());
+use crate::catalog::items::Recipe;
+use crate::error::{AppError, AppResult};
+use crate::search::{find_exact_match, find_substring_matches, parse_search_terms};
+use clap::Args;
+use dialoguer::FuzzySelect;
+use dialoguer::theme::ColorfulTheme;
+use std::io::{self, IsTerminal, Write};
+
+/// Extras shown with the recipe's nutrition table, and how an ambiguous search is handled
+#[derive(Args, Clone, Default)]
+pub struct DisplayArgs {
+    #[arg(long, help = "Compare against this person's daily targets")]
+    person: Option<String>,
+    #[arg(
+        long,
+        help = "Show each ingredient's share of calories and protein, and the energy split"
+    )]
+    breakdown: bool,
+    #[arg(
+        long = "per-100g",
+        help = "Add nutrition per 100 g of the finished dish (cooked weight if the recipe has one)"
+    )]
+    per_100g: bool,
+    #[arg(
+        long,
+        help = "List the matches instead of prompting when the search matches several recipes"
+    )]
+    no_interactive: bool,
+    #[arg(
+        long,
+        value_enum,
+        default_value_t = RecipeFormat::Table,
+        help = "Output format; csv and json are unrounded (--person and --breakdown only apply to the table)"
+    )]
+    format: RecipeFormat,
+}
+
+pub fn run(recipe_name: &str, scale: &ScaleArgs, display: &DisplayArgs) -> AppResult<()> {
+    show_matching(&catalog::load_recipes()?, recipe_name, scale, display)
+}
+
+/// Show the recipe again after every save to the catalog files
+pub fn run_watch(recipe_name: &str, scale: &ScaleArgs, display: &DisplayArgs) -> AppResult<()> {
+    // A prompt on every redraw would get in the way of editing
+    let display = DisplayArgs {
+        no_interactive: true,
+        ..display.clone()
+    };
+    // The target rows come from targets.jsonc, with or without --person
+    watch::watch(vec![catalog::targets_file()?], || {
+        watch::clear_screen()?;
+        run(recipe_name, scale, &display)
+    })
+}
+
+/// Show the recipe with this exact name, or the one the search terms match
+pub(crate) fn show_matching(
+    recipes: &[Recipe],
+    recipe_name: &str,
+    scale: &ScaleArgs,
+    display: &DisplayArgs,
+) -> AppResult<()> {
+    if let Some(recipe) = find_exact_match(recipes, recipe_name) {
+        return show_recipe(recipe, scale, display);
     }
 
     let search_terms = parse_search_terms(recipe_name);
-    let matches = find_substring_matches(&recipes, &search_terms);
+    let matches = find_substring_matches(recipes, &search_terms);
 
     match matches.len() {
+        1 => show_recipe(matches[0], scale, display),
+        n if n > 1 && offers_picker(display, is_interactive_terminal()) => {
+            match pick_recipe(recipe_name, &matches)? {
+                Some(recipe) => show_recipe(recipe, scale, display),
+                None => Ok(()),
+            }
+        }
+        _ if display.format.is_machine_readable() => Err(AppError::RecipeNotFound {
+            query: recipe_name.to_string(),
+            candidates: matches.iter().map(|r| r.name.clone()).collect(),
+        }),
         0 => {
             let available: Vec<String> = recipes.iter().map(|r| r.name.clone()).collect();
             if available.is_empty() {
@@ -35,14 +106,6 @@ pub fn run(recipe_name: &str) -> AppResult<()> {
             }
             Ok(())
         }
-        1 => {
-            let recipe = matches[0];
-            println!("Recipe: {}", recipe.name);
-            println!();
-            let mut stdout = io::stdout();
-            render_nutrition_table(&recipe.ingredients, &mut stdout)?;
-            Ok(())
-        }
         _ => {
             let available: Vec<String> = matches.iter().map(|r| r.name.clone()).collect();
             const MAX_DISPLAYED: usize = 3;
@@ -72,3 +135,135 @@ pub fn run(recipe_name: &str) -> AppResult<()> {
         }
     }
 }
+
+/// Whether stdin, stdout and stderr (where the picker is drawn) are all a terminal
+fn is_interactive_terminal() -> bool {
+    io::stdin().is_terminal() && io::stdout().is_terminal() && io::stderr().is_terminal()
+}
+
+/// Whether an ambiguous search prompts for a choice instead of listing the matches
+fn offers_picker(display: &DisplayArgs, interactive_terminal: bool) -> bool {
+    interactive_terminal && !display.no_interactive
+}
+
+/// Fuzzy-select prompt over the matching recipes; `None` when the user cancels
+///
+/// The prompt is drawn on stderr, so only the chosen recipe ends up on stdout.
+fn pick_recipe<'a>(query: &str, matches: &[&'a Recipe]) -> AppResult<Option<&'a Recipe>> {
+    let names: Vec<&str> = matches.iter().map(|r| r.name.as_str()).collect();
+    let choice = FuzzySelect::with_theme(&ColorfulTheme::default())
+        .with_prompt(format!(
+            "{} recipes match '{}', pick one (Esc to cancel)",
+            matches.len(),
+            query
+        ))
+        .items(&names)
+        .default(0)
+        .interact_opt()
+        .map_err(|dialoguer::Error::IO(error)| AppError::Io(error))?;
+    Ok(choice.map(|index| matches[index]))
+}
+
+fn show_recipe(recipe: &Recipe, scale: &ScaleArgs, display: &DisplayArgs) -> AppResult<()> {
+    let factor = scale.factor_for(recipe)?;
+    let scaled = factor.map(|factor| recipe.scaled(factor));
+    let shown = scaled.as_ref().unwrap_or(recipe);
+
+    let mut stdout = io::stdout();
+    if scale.card {
+        render_kitchen_card(shown, factor, &mut stdout)?;
+        return Ok(());
+    }
+    match display.format {
+        RecipeFormat::Table => show_table(shown, factor, scale, display)?,
+        RecipeFormat::Plain => format::write_plain(shown, factor, display.per_100g, &mut stdout)?,
+        RecipeFormat::Markdown => {
+            format::write_markdown(shown, factor, display.per_100g, &mut stdout)?
+        }
+        RecipeFormat::Csv => format::write_csv(shown, display.per_100g, &mut stdout)?,
+        RecipeFormat::Json => format::write_json(shown, factor, &mut stdout)?,
+    }
+    Ok(())
+}
+
+/// The colored nutrition table with the extras asked for
+fn show_table(
+    shown: &Recipe,
+    factor: Option<f64>,
+    scale: &ScaleArgs,
+    display: &DisplayArgs,
+) -> AppResult<()> {
+    let targets = catalog::load_targets(display.person.as_deref())?;
+    let mut stdout = io::stdout();
+
+    writeln!(stdout, "Recipe: {}", shown.name)?;
+    match (factor, &scale.scale_to) {
+        (Some(factor), Some(target)) => writeln!(stdout, "Scaled ×{:.2} to {}", factor, target)?,
+        (Some(factor), None) => writeln!(stdout, "Scaled ×{:.2}", factor)?,
+        (None, _) => {}
+    }
+    writeln!(stdout)?;
+    let options = TableOptions {
+        daily_targets: targets.as_ref(),
+        energy_breakdown: display.breakdown,
+        per_100g_of: display.per_100g.then(|| shown.finished_grams()),
+        ..Default::default()
+    };
+    render_nutrition_table(&shown.ingredients, &options, &mut stdout)?;
+    Ok(())
+}
+
+/// Plain ingredient list with weights, meant to be printed and kept in the kitchen
+fn render_kitchen_card<W: Write>(
+    recipe: &Recipe,
+    factor: Option<f64>,
+    writer: &mut W,
+) -> io::Result<()> {
+    match factor {
+        Some(factor) => writeln!(writer, "{} (×{:.2})", recipe.name, factor)?,
+        None => writeln!(writer, "{}", recipe.name)?,
+    }
+    writeln!(writer)?;
+
+    let amounts: Vec<String> = recipe
+        .ingredients
+        .iter()
+        .map(|i| format!("{:.1} g", i.grams))
+        .collect();
+    let width = amounts.iter().map(|a| a.len()).max().unwrap_or(0);
+    for (amount, ingredient) in amounts.iter().zip(&recipe.ingredients) {
+        writeln!(
+            writer,
+            "  {:>width$}  {}",
+            amount,
+            ingredient.ingredient.name,
+            width = width
+        )?;
+    }
+
+    let totals = recipe.totals();
+    writeln!(writer)?;
+    writeln!(
+        writer,
+        "Total: {:.1} g, {:.0} kcal, {:.1} g protein",
+        totals.grams, totals.calories, totals.protein
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_picker_only_on_a_terminal_without_no_interactive() {
+        let display = DisplayArgs::default();
+        assert!(offers_picker(&display, true));
+        assert!(!offers_picker(&display, false));
+
+        let no_interactive = DisplayArgs {
+            no_interactive: true,
+            ..Default::default()
+        };
+        assert!(!offers_picker(&no_interactive, true));
+    }
+}
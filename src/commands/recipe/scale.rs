@@ -0,0 +1,148 @@
+use crate::catalog::items::Recipe;
+use crate::error::{AppError, AppResult};
+use clap::Args;
+use std::fmt;
+
+#[derive(Args, Debug, Default)]
+pub struct ScaleArgs {
+    #[arg(
+        long,
+        value_name = "TARGET",
+        value_parser = parse_scale_target,
+        conflicts_with = "factor",
+        help = "Scale all amounts to reach a target: 600kcal, 500g (cooked weight if the recipe has one) or 40g-protein"
+    )]
+    pub scale_to: Option<ScaleTarget>,
+    #[arg(
+        long,
+        value_parser = parse_factor,
+        help = "Multiply all amounts by a factor (e.g., 1.5)"
+    )]
+    pub factor: Option<f64>,
+    #[arg(
+        long,
+        help = "Print the ingredient weights as a kitchen card instead of the nutrition table"
+    )]
+    pub card: bool,
+}
+
+impl ScaleArgs {
+    /// Factor to apply to the recipe, `None` when no scaling was requested
+    pub(super) fn factor_for(&self, recipe: &Recipe) -> AppResult<Option<f64>> {
+        match (&self.scale_to, self.factor) {
+            (Some(target), _) => target.factor_for(recipe).map(Some),
+            (None, factor) => Ok(factor),
+        }
+    }
+}
+
+/// Amount a recipe should be scaled to
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ScaleTarget {
+    Calories(f64),
+    Weight(f64),
+    Protein(f64),
+}
+
+impl ScaleTarget {
+    fn value(&self) -> f64 {
+        match self {
+            ScaleTarget::Calories(value)
+            | ScaleTarget::Weight(value)
+            | ScaleTarget::Protein(value) => *value,
+        }
+    }
+
+    /// The recipe's current amount; weight is that of the finished dish, as in portions
+    fn current(&self, recipe: &Recipe) -> f64 {
+        match self {
+            ScaleTarget::Calories(_) => recipe.totals().calories,
+            ScaleTarget::Weight(_) => recipe.finished_grams(),
+            ScaleTarget::Protein(_) => recipe.totals().protein,
+        }
+    }
+
+    fn factor_for(&self, recipe: &Recipe) -> AppResult<f64> {
+        let current = self.current(recipe);
+        if current <= 0.0 {
+            return Err(AppError::CannotScale {
+                recipe: recipe.name.clone(),
+                target: self.to_string(),
+            });
+        }
+        Ok(self.value() / current)
+    }
+}
+
+impl fmt::Display for ScaleTarget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ScaleTarget::Calories(kcal) => write!(f, "{} kcal", kcal),
+            ScaleTarget::Weight(grams) => write!(f, "{} g", grams),
+            ScaleTarget::Protein(grams) => write!(f, "{} g protein", grams),
+        }
+    }
+}
+
+pub(crate) fn parse_scale_target(value: &str) -> Result<ScaleTarget, String> {
+    let normalized = value.trim().to_lowercase().replace(' ', "");
+    let split = normalized
+        .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+        .unwrap_or(normalized.len());
+    let (amount, unit) = normalized.split_at(split);
+
+    let amount: f64 = amount
+        .parse()
+        .ok()
+        .filter(|amount: &f64| *amount > 0.0)
+        .ok_or_else(|| format!("expected a positive amount, got '{}'", value))?;
+
+    match unit {
+        "kcal" => Ok(ScaleTarget::Calories(amount)),
+        "g" => Ok(ScaleTarget::Weight(amount)),
+        "g-protein" | "gprotein" => Ok(ScaleTarget::Protein(amount)),
+        _ => Err(format!(
+            "unknown unit in '{}' (use kcal, g or g-protein, e.g. 600kcal)",
+            value
+        )),
+    }
+}
+
+pub(crate) fn parse_factor(value: &str) -> Result<f64, String> {
+    value
+        .trim()
+        .parse()
+        .ok()
+        .filter(|factor: &f64| factor.is_finite() && *factor > 0.0)
+        .ok_or_else(|| format!("expected a positive number, got '{}'", value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_scale_target_units() {
+        assert_eq!(
+            parse_scale_target("600kcal"),
+            Ok(ScaleTarget::Calories(600.0))
+        );
+        assert_eq!(parse_scale_target("500 g"), Ok(ScaleTarget::Weight(500.0)));
+        assert_eq!(
+            parse_scale_target("40g-protein"),
+            Ok(ScaleTarget::Protein(40.0))
+        );
+        assert_eq!(
+            parse_scale_target("12.5G-Protein"),
+            Ok(ScaleTarget::Protein(12.5))
+        );
+    }
+
+    #[test]
+    fn test_parse_scale_target_rejects_invalid_input() {
+        assert!(parse_scale_target("600").is_err());
+        assert!(parse_scale_target("kcal").is_err());
+        assert!(parse_scale_target("0kcal").is_err());
+        assert!(parse_scale_target("40g-fat").is_err());
+    }
+}
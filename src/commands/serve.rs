@@ -0,0 +1,216 @@
+use super::kitchen_ref::render_html;
+use super::list::ListEntry;
+use super::recipe::{NutritionEntry, RecipeNutrition, parse_factor};
+use super::watch::LiveCatalog;
+use crate::catalog::items::{Ingredient, Recipe};
+use crate::error::AppResult;
+use crate::search::resolve_recipe;
+use clap::Args;
+use serde::Serialize;
+use std::io;
+use std::net::Ipv4Addr;
+use tiny_http::{Header, Method, Response, Server};
+
+#[derive(Args)]
+pub struct ServeArgs {
+    #[arg(
+        long,
+        default_value_t = 8080,
+        help = "Port to listen on (0 picks a free one)"
+    )]
+    port: u16,
+    #[arg(
+        long,
+        help = "Accept connections from other devices on the local network, not just this computer"
+    )]
+    lan: bool,
+}
+
+/// Reloads the kitchen reference page once the catalog has been reloaded
+const LIVE_RELOAD_SCRIPT: &str = r#"<script>
+(function () {
+  let seen = null;
+  setInterval(async function () {
+    try {
+      const response = await fetch("/api/generation");
+      const { generation } = await response.json();
+      if (seen !== null && generation !== seen) location.reload();
+      seen = generation;
+    } catch (e) {}
+  }, 2000);
+})();
+</script>
+"#;
+
+#[derive(Serialize)]
+struct IngredientEntry<'a> {
+    id: &'a str,
+    name: &'a str,
+    per_100g: NutritionEntry,
+    aisle: Option<&'a str>,
+    package_grams: Option<f64>,
+}
+
+#[derive(Serialize)]
+struct ErrorEntry<'a> {
+    error: &'a str,
+}
+
+/// What to send back for a request
+struct Reply {
+    status: u16,
+    content_type: &'static str,
+    body: String,
+}
+
+impl Reply {
+    fn html(body: String) -> Self {
+        Reply {
+            status: 200,
+            content_type: "text/html; charset=utf-8",
+            body,
+        }
+    }
+
+    fn json<T: Serialize>(value: &T) -> Self {
+        Reply {
+            status: 200,
+            content_type: "application/json",
+            body: serde_json::to_string_pretty(value).expect("API entries serialize"),
+        }
+    }
+
+    fn error(status: u16, message: &str) -> Self {
+        Reply {
+            status,
+            ..Reply::json(&ErrorEntry { error: message })
+        }
+    }
+}
+
+/// Serve the kitchen reference and a JSON API over the catalog, reloaded when its files change
+pub fn run(args: &ServeArgs) -> AppResult<()> {
+    let mut catalog = LiveCatalog::load()?;
+    let host = if args.lan {
+        Ipv4Addr::UNSPECIFIED
+    } else {
+        Ipv4Addr::LOCALHOST
+    };
+    let server = Server::http((host, args.port))
+        .map_err(|e| io::Error::other(format!("Cannot listen on {}:{}: {}", host, args.port, e)))?;
+    let port = server
+        .server_addr()
+        .to_ip()
+        .map_or(args.port, |address| address.port());
+
+    if args.lan {
+        println!(
+            "Serving at http://{}:{}/ on all network interfaces; other devices use this computer's address (Ctrl-C to stop)",
+            host, port
+        );
+    } else {
+        println!("Serving at http://{}:{}/ (Ctrl-C to stop)", host, port);
+    }
+
+    for request in server.incoming_requests() {
+        catalog.refresh();
+        let reply = route(&catalog, request.method(), request.url());
+        let content_type = Header::from_bytes("Content-Type", reply.content_type)
+            .expect("content types are valid header values");
+        let response = Response::from_string(reply.body)
+            .with_status_code(reply.status)
+            .with_header(content_type);
+        // A client that went away is no reason to stop serving the others
+        let _ = request.respond(response);
+    }
+    Ok(())
+}
+
+fn route(live: &LiveCatalog, method: &Method, url: &str) -> Reply {
+    if *method != Method::Get {
+        return Reply::error(405, "Only GET requests are supported");
+    }
+    let (path, query) = url.split_once('?').unwrap_or((url, ""));
+    let recipes = &live.catalog.recipes;
+
+    match path {
+        "/" => Reply::html(
+            render_html(recipes).replace("</body>", &format!("{}</body>", LIVE_RELOAD_SCRIPT)),
+        ),
+        "/api/recipes" => Reply::json(
+            &recipes
+                .iter()
+                .map(|recipe| ListEntry::new(recipe, &recipe.totals()))
+                .collect::<Vec<_>>(),
+        ),
+        "/api/ingredients" => Reply::json(
+            &live
+                .catalog
+                .ingredients
+                .iter()
+                .map(ingredient_entry)
+                .collect::<Vec<_>>(),
+        ),
+        "/api/generation" => Reply::json(&serde_json::json!({ "generation": live.generation })),
+        _ => match path.strip_prefix("/api/recipes/") {
+            Some(name) => recipe_nutrition(recipes, &percent_decode(name), query),
+            None => Reply::error(404, &format!("Nothing at {}", path)),
+        },
+    }
+}
+
+/// A recipe's per-ingredient and total nutrition, scaled by the `scale` query parameter
+fn recipe_nutrition(recipes: &[Recipe], name: &str, query: &str) -> Reply {
+    let factor = match query_param(query, "scale").map(|value| parse_factor(&value)) {
+        None => 1.0,
+        Some(Ok(factor)) => factor,
+        Some(Err(message)) => return Reply::error(400, &format!("Invalid scale: {}", message)),
+    };
+    let recipe = match resolve_recipe(recipes, name) {
+        Ok(recipe) => recipe.scaled(factor),
+        Err(e) => return Reply::error(404, &e.to_string()),
+    };
+    Reply::json(&RecipeNutrition::new(&recipe, factor))
+}
+
+fn ingredient_entry(ingredient: &Ingredient) -> IngredientEntry<'_> {
+    IngredientEntry {
+        id: &ingredient.id,
+        name: &ingredient.name,
+        per_100g: ingredient.nutrition_for(100.0).into(),
+        aisle: ingredient.aisle.as_deref(),
+        package_grams: ingredient.package_grams,
+    }
+}
+
+fn query_param(query: &str, key: &str) -> Option<String> {
+    query
+        .split('&')
+        .filter_map(|pair| pair.split_once('='))
+        .find(|(name, _)| *name == key)
+        .map(|(_, value)| percent_decode(&value.replace('+', " ")))
+}
+
+/// Undo `%XX` escapes, as browsers send names with spaces
+fn percent_decode(text: &str) -> String {
+    let bytes = text.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut index = 0;
+    while index < bytes.len() {
+        let escaped = (bytes[index] == b'%')
+            .then(|| text.get(index + 1..index + 3))
+            .flatten()
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match escaped {
+            Some(byte) => {
+                decoded.push(byte);
+                index += 3;
+            }
+            None => {
+                decoded.push(bytes[index]);
+                index += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
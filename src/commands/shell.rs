@@ -0,0 +1,345 @@
+use super::compare::compare;
+use super::ingredient::lookup;
+use super::recipe::{DisplayArgs, ScaleArgs, parse_factor, parse_scale_target, show_matching};
+use super::watch::LiveCatalog;
+use crate::catalog::Catalog;
+use crate::error::{AppError, AppResult};
+use clap::{Parser, Subcommand};
+use rustyline::completion::{Completer, Pair};
+use rustyline::error::ReadlineError;
+use rustyline::highlight::Highlighter;
+use rustyline::hint::Hinter;
+use rustyline::history::DefaultHistory;
+use rustyline::validate::Validator;
+use rustyline::{Context, Editor, Helper};
+use std::cell::RefCell;
+use std::io::{self, IsTerminal};
+use std::path::PathBuf;
+use std::rc::Rc;
+
+const PROMPT: &str = "nutriterm> ";
+const HISTORY_FILE: &str = ".nutriterm_history";
+const COMMANDS: [&str; 8] = [
+    "recipe",
+    "ingredient",
+    "compare",
+    "scale",
+    "reload",
+    "help",
+    "exit",
+    "quit",
+];
+
+/// One line typed at the prompt
+#[derive(Parser)]
+#[command(
+    name = "",
+    no_binary_name = true,
+    disable_version_flag = true,
+    help_template = "Commands:\n{subcommands}"
+)]
+struct ShellLine {
+    #[command(subcommand)]
+    command: ShellCommand,
+}
+
+#[derive(Subcommand)]
+enum ShellCommand {
+    #[command(about = "Display nutrition for a recipe (same options as 'nutriterm recipe')")]
+    Recipe {
+        #[arg(required = true, help = "Recipe name or search terms")]
+        name: Vec<String>,
+        #[command(flatten)]
+        scale: ScaleArgs,
+        #[command(flatten)]
+        display: DisplayArgs,
+    },
+    #[command(about = "Show nutrition for an ingredient and the recipes using it")]
+    Ingredient {
+        #[arg(required = true, help = "Ingredient ID, name or search terms")]
+        query: Vec<String>,
+    },
+    #[command(about = "Compare two or more recipes side by side")]
+    Compare {
+        #[arg(required = true, num_args = 2.., help = "Recipe names or search terms")]
+        recipes: Vec<String>,
+    },
+    #[command(
+        about = "Scale a recipe to a target (600kcal, 500g, 40g-protein) or by a factor (1.5)"
+    )]
+    Scale {
+        #[arg(help = "Recipe name or search terms (quote names with spaces)")]
+        recipe: String,
+        #[arg(help = "Target or factor, e.g. 600kcal or 1.5")]
+        target: String,
+        #[arg(long, help = "Print the ingredient weights as a kitchen card")]
+        card: bool,
+    },
+    #[command(about = "Load the catalog files again")]
+    Reload,
+    #[command(about = "Leave the shell", alias = "quit")]
+    Exit,
+}
+
+/// Interactive prompt running commands against a catalog loaded once
+pub fn run() -> AppResult<()> {
+    let session = Rc::new(RefCell::new(LiveCatalog::load()?));
+    let mut editor: Editor<ShellHelper, DefaultHistory> = Editor::new().map_err(readline_error)?;
+    editor.set_helper(Some(ShellHelper {
+        session: Rc::clone(&session),
+    }));
+    // Lines piped in from scripts are not worth remembering
+    let history = std::env::var_os("HOME")
+        .filter(|_| io::stdin().is_terminal())
+        .map(|home| PathBuf::from(home).join(HISTORY_FILE));
+    if let Some(history) = &history {
+        // A missing history file just means a first session
+        let _ = editor.load_history(history);
+    }
+
+    println!("nutriterm shell: type 'help' for commands, 'exit' or Ctrl-D to leave");
+    loop {
+        let line = match editor.readline(PROMPT) {
+            Ok(line) => line,
+            Err(ReadlineError::Interrupted) => continue,
+            Err(ReadlineError::Eof) => break,
+            Err(e) => return Err(readline_error(e)),
+        };
+        if line.trim().is_empty() {
+            continue;
+        }
+        let _ = editor.add_history_entry(line.as_str());
+
+        let words = match shell_words::split(&line) {
+            Ok(words) => words,
+            Err(e) => {
+                eprintln!("Error: {}", e);
+                continue;
+            }
+        };
+        let command = match ShellLine::try_parse_from(words) {
+            Ok(parsed) => parsed.command,
+            Err(e) => {
+                let _ = e.print();
+                continue;
+            }
+        };
+        if matches!(command, ShellCommand::Exit) {
+            break;
+        }
+
+        let mut session = session.borrow_mut();
+        session.refresh();
+        if let Err(e) = execute(&mut session, &command) {
+            eprintln!("Error: {}", e);
+        }
+    }
+
+    if let Some(history) = &history {
+        let _ = editor.save_history(history);
+    }
+    Ok(())
+}
+
+fn execute(session: &mut LiveCatalog, command: &ShellCommand) -> AppResult<()> {
+    let recipes = &session.catalog.recipes;
+    match command {
+        ShellCommand::Recipe {
+            name,
+            scale,
+            display,
+        } => show_matching(recipes, &name.join(" "), scale, display),
+        ShellCommand::Ingredient { query } => lookup(&session.catalog, &query.join(" ")),
+        ShellCommand::Compare { recipes: queries } => compare(recipes, queries),
+        ShellCommand::Scale {
+            recipe,
+            target,
+            card,
+        } => {
+            let scale = parse_scale(target, *card)?;
+            show_matching(recipes, recipe, &scale, &DisplayArgs::default())
+        }
+        ShellCommand::Reload => {
+            session.reload()?;
+            println!(
+                "Reloaded {} recipes and {} ingredients",
+                session.catalog.recipes.len(),
+                session.catalog.ingredients.len()
+            );
+            Ok(())
+        }
+        ShellCommand::Exit => Ok(()),
+    }
+}
+
+/// A factor like "1.5" or "x1.5", otherwise a target like "600kcal"
+fn parse_scale(target: &str, card: bool) -> AppResult<ScaleArgs> {
+    let factor = target.trim_start_matches(['x', 'X', '×']);
+    let scale = match parse_factor(factor) {
+        Ok(factor) => ScaleArgs {
+            factor: Some(factor),
+            card,
+            ..Default::default()
+        },
+        Err(_) => ScaleArgs {
+            scale_to: Some(
+                parse_scale_target(target)
+                    .map_err(|message| AppError::InvalidArgument { message })?,
+            ),
+            card,
+            ..Default::default()
+        },
+    };
+    Ok(scale)
+}
+
+fn readline_error(error: ReadlineError) -> AppError {
+    match error {
+        ReadlineError::Io(error) => error.into(),
+        error => std::io::Error::other(error).into(),
+    }
+}
+
+/// Tab completion of commands, recipe names and ingredient IDs
+struct ShellHelper {
+    session: Rc<RefCell<LiveCatalog>>,
+}
+
+impl Completer for ShellHelper {
+    type Candidate = Pair;
+
+    fn complete(
+        &self,
+        line: &str,
+        pos: usize,
+        _ctx: &Context<'_>,
+    ) -> rustyline::Result<(usize, Vec<Pair>)> {
+        let session = self.session.borrow();
+        Ok(complete_line(&session.catalog, &line[..pos]))
+    }
+}
+
+impl Hinter for ShellHelper {
+    type Hint = String;
+}
+
+impl Highlighter for ShellHelper {}
+
+impl Validator for ShellHelper {}
+
+impl Helper for ShellHelper {}
+
+/// Where the word being completed starts and the candidates for it
+///
+/// Names with spaces are completed in quotes so they stay one argument.
+fn complete_line(catalog: &Catalog, line: &str) -> (usize, Vec<Pair>) {
+    let (start, previous, word) = split_current_word(line);
+    let prefix = word.trim_start_matches(['"', '\'']).to_lowercase();
+    let candidate = |name: &str| {
+        let replacement = if name.contains(char::is_whitespace) || word.starts_with(['"', '\'']) {
+            format!("\"{}\"", name)
+        } else {
+            name.to_string()
+        };
+        Pair {
+            display: name.to_string(),
+            replacement,
+        }
+    };
+
+    if word.starts_with('-') {
+        return (start, Vec::new());
+    }
+    let names: Vec<&str> = match previous.first().map(String::as_str) {
+        None => COMMANDS.to_vec(),
+        Some("recipe" | "compare") => catalog.recipes.iter().map(|r| r.name.as_str()).collect(),
+        Some("scale") if previous.len() == 1 => {
+            catalog.recipes.iter().map(|r| r.name.as_str()).collect()
+        }
+        Some("ingredient") => catalog.ingredients.iter().map(|i| i.id.as_str()).collect(),
+        Some(_) => Vec::new(),
+    };
+    let candidates = names
+        .into_iter()
+        .filter(|name| name.to_lowercase().starts_with(&prefix))
+        .map(candidate)
+        .collect();
+    (start, candidates)
+}
+
+/// Split at the last unquoted whitespace into the finished words and the word being typed
+fn split_current_word(line: &str) -> (usize, Vec<String>, &str) {
+    let mut quote = None;
+    let mut start = 0;
+    for (index, c) in line.char_indices() {
+        match (quote, c) {
+            (Some(open), c) if c == open => quote = None,
+            (None, '"' | '\'') => quote = Some(c),
+            (None, c) if c.is_whitespace() => start = index + c.len_utf8(),
+            _ => {}
+        }
+    }
+    let previous = shell_words::split(&line[..start]).unwrap_or_default();
+    (start, previous, &line[start..])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::catalog::items::{Ingredient, Recipe};
+
+    fn catalog() -> Catalog {
+        let ingredient = |id: &str| Ingredient {
+            id: id.to_string(),
+            name: id.replace('_', " "),
+            carbs_per_100g: 0.0,
+            protein_per_100g: 0.0,
+            fat_per_100g: 0.0,
+            fiber_per_100g: 0.0,
+            aisle: None,
+            package_grams: None,
+        };
+        let recipe = |name: &str| Recipe {
+            name: name.to_string(),
+            tags: Vec::new(),
+            ingredients: Vec::new(),
+            cooked_grams: None,
+        };
+        Catalog {
+            ingredients: vec![ingredient("brown_rice"), ingredient("broccoli")],
+            recipes: vec![recipe("Chicken Rice Bowl"), recipe("Porridge")],
+        }
+    }
+
+    fn replacements(line: &str) -> (usize, Vec<String>) {
+        let (start, candidates) = complete_line(&catalog(), line);
+        (
+            start,
+            candidates.into_iter().map(|c| c.replacement).collect(),
+        )
+    }
+
+    #[test]
+    fn test_completes_commands_recipes_and_ingredients() {
+        assert_eq!(replacements("sc"), (0, vec!["scale".to_string()]));
+        assert_eq!(
+            replacements("recipe chi"),
+            (7, vec!["\"Chicken Rice Bowl\"".to_string()])
+        );
+        assert_eq!(
+            replacements("ingredient br"),
+            (11, vec!["brown_rice".to_string(), "broccoli".to_string()])
+        );
+        assert_eq!(
+            replacements("compare Porridge 'ch"),
+            (17, vec!["\"Chicken Rice Bowl\"".to_string()])
+        );
+    }
+
+    #[test]
+    fn test_completes_only_the_recipe_argument_of_scale() {
+        assert_eq!(replacements("scale por").1, vec!["Porridge".to_string()]);
+        assert!(replacements("scale Porridge 6").1.is_empty());
+        assert!(replacements("recipe --fa").1.is_empty());
+    }
+}
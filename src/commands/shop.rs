@@ -0,0 +1,215 @@
+use super::display::{escape_html, format_number_with_unit};
+use crate::catalog;
+use crate::catalog::items::{Ingredient, Recipe};
+use crate::error::AppResult;
+use crate::search::{find_exact_match, resolve_recipe};
+use clap::{Args, ValueEnum};
+use std::collections::BTreeMap;
+use std::io::{self, Write};
+
+#[derive(Args)]
+pub struct ShopArgs {
+    #[arg(
+        required = true,
+        add = super::completions::recipe_names(),
+        help = "Recipes to shop for, each optionally with a multiplier (e.g., \"Chicken Rice Bowl x3\" porridge×2)"
+    )]
+    recipes: Vec<String>,
+    #[arg(long, value_enum, default_value_t = ShopFormat::Plain, help = "Output format")]
+    format: ShopFormat,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum ShopFormat {
+    Plain,
+    Markdown,
+    Html,
+}
+
+/// Heading for ingredients without an aisle, listed after all named aisles
+const OTHER_AISLE: &str = "Other";
+
+/// Total amount of one ingredient across all chosen recipes
+struct ShopItem<'a> {
+    ingredient: &'a Ingredient,
+    grams: f64,
+}
+
+impl ShopItem<'_> {
+    /// e.g. "450.0 g" or "1,200.0 g (3 × 500 g packages)"
+    fn amount(&self) -> String {
+        let grams = format_number_with_unit(self.grams, "g");
+        match self.ingredient.package_grams {
+            Some(package) => {
+                let count = packages_needed(self.grams, package);
+                let noun = if count == 1 { "package" } else { "packages" };
+                format!("{} ({} × {} g {})", grams, count, package, noun)
+            }
+            None => grams,
+        }
+    }
+}
+
+struct ShoppingList<'a> {
+    /// e.g. "Chicken Rice Bowl ×3, Porridge"
+    title: String,
+    /// Aisles by name, each with its items sorted by ingredient name
+    aisles: Vec<(&'a str, Vec<ShopItem<'a>>)>,
+}
+
+pub fn run(args: &ShopArgs) -> AppResult<()> {
+    let recipes = catalog::load_recipes()?;
+    let mut selection = Vec::new();
+    for query in &args.recipes {
+        let (name, factor) = parse_recipe_with_multiplier(&recipes, query);
+        selection.push((resolve_recipe(&recipes, name)?, factor));
+    }
+
+    let list = build_shopping_list(&selection);
+    let mut stdout = io::stdout();
+    match args.format {
+        ShopFormat::Plain => render_plain(&list, &mut stdout)?,
+        ShopFormat::Markdown => render_markdown(&list, &mut stdout)?,
+        ShopFormat::Html => render_html(&list, &mut stdout)?,
+    }
+    Ok(())
+}
+
+/// Split "Porridge x2" or "porridge×2" into the recipe query and multiplier
+///
+/// A query that exactly names a recipe is never split, so names ending in
+/// something like "x2" still work.
+fn parse_recipe_with_multiplier<'q>(recipes: &[Recipe], query: &'q str) -> (&'q str, f64) {
+    if find_exact_match(recipes, query).is_some() {
+        return (query, 1.0);
+    }
+    let split = query
+        .char_indices()
+        .rev()
+        .find(|(_, c)| *c == 'x' || *c == 'X' || *c == '×')
+        .and_then(|(index, c)| {
+            let factor = query[index + c.len_utf8()..].trim().parse::<f64>().ok()?;
+            let name = query[..index].trim_end();
+            (factor.is_finite() && factor > 0.0 && !name.is_empty()).then_some((name, factor))
+        });
+    split.unwrap_or((query, 1.0))
+}
+
+fn build_shopping_list<'a>(selection: &[(&'a Recipe, f64)]) -> ShoppingList<'a> {
+    let mut items: Vec<ShopItem<'a>> = Vec::new();
+    for (recipe, factor) in selection {
+        for entry in &recipe.ingredients {
+            let grams = entry.grams * factor;
+            match items
+                .iter_mut()
+                .find(|item| item.ingredient.id == entry.ingredient.id)
+            {
+                Some(item) => item.grams += grams,
+                None => items.push(ShopItem {
+                    ingredient: &entry.ingredient,
+                    grams,
+                }),
+            }
+        }
+    }
+
+    let mut by_aisle: BTreeMap<Option<&'a str>, Vec<ShopItem<'a>>> = BTreeMap::new();
+    for item in items {
+        by_aisle
+            .entry(item.ingredient.aisle.as_deref())
+            .or_default()
+            .push(item);
+    }
+    // Named aisles alphabetically, then ingredients without an aisle
+    let (named, unassigned): (Vec<_>, Vec<_>) =
+        by_aisle.into_iter().partition(|(aisle, _)| aisle.is_some());
+    let aisles = named
+        .into_iter()
+        .chain(unassigned)
+        .map(|(aisle, mut items)| {
+            items.sort_by(|a, b| a.ingredient.name.cmp(&b.ingredient.name));
+            (aisle.unwrap_or(OTHER_AISLE), items)
+        })
+        .collect();
+
+    let title = selection
+        .iter()
+        .map(|(recipe, factor)| match factor {
+            1.0 => recipe.name.clone(),
+            factor => format!("{} ×{}", recipe.name, factor),
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+    ShoppingList { title, aisles }
+}
+
+/// Whole packages needed for an amount, ignoring floating point noise
+fn packages_needed(grams: f64, package_grams: f64) -> u64 {
+    ((grams / package_grams) - 1e-9).ceil().max(1.0) as u64
+}
+
+fn render_plain<W: Write>(list: &ShoppingList, writer: &mut W) -> io::Result<()> {
+    writeln!(writer, "Shopping list: {}", list.title)?;
+    let width = list
+        .aisles
+        .iter()
+        .flat_map(|(_, items)| items)
+        .map(|i| i.ingredient.name.chars().count())
+        .max()
+        .unwrap_or(0);
+    for (aisle, items) in &list.aisles {
+        writeln!(writer)?;
+        writeln!(writer, "{}", aisle)?;
+        for item in items {
+            writeln!(
+                writer,
+                "  {:<width$}  {}",
+                item.ingredient.name,
+                item.amount(),
+                width = width
+            )?;
+        }
+    }
+    Ok(())
+}
+
+fn render_markdown<W: Write>(list: &ShoppingList, writer: &mut W) -> io::Result<()> {
+    writeln!(writer, "# Shopping list")?;
+    writeln!(writer)?;
+    writeln!(writer, "For: {}", list.title)?;
+    for (aisle, items) in &list.aisles {
+        writeln!(writer)?;
+        writeln!(writer, "## {}", aisle)?;
+        writeln!(writer)?;
+        for item in items {
+            writeln!(writer, "- [ ] {}: {}", item.ingredient.name, item.amount())?;
+        }
+    }
+    Ok(())
+}
+
+fn render_html<W: Write>(list: &ShoppingList, writer: &mut W) -> io::Result<()> {
+    writeln!(writer, "<!DOCTYPE html>")?;
+    writeln!(writer, "<html>")?;
+    writeln!(writer, "<head><title>Shopping List</title></head>")?;
+    writeln!(writer, "<body>")?;
+    writeln!(writer, "<h1>Shopping List</h1>")?;
+    writeln!(writer, "<p>For: {}</p>", escape_html(&list.title))?;
+    for (aisle, items) in &list.aisles {
+        writeln!(writer)?;
+        writeln!(writer, "<h2>{}</h2>", escape_html(aisle))?;
+        writeln!(writer, "<ul>")?;
+        for item in items {
+            writeln!(
+                writer,
+                "<li><label><input type=\"checkbox\"> {}: {}</label></li>",
+                escape_html(&item.ingredient.name),
+                escape_html(&item.amount())
+            )?;
+        }
+        writeln!(writer, "</ul>")?;
+    }
+    writeln!(writer)?;
+    writeln!(writer, "</body>")?;
+    writeln!(writer, "</html>")
+}
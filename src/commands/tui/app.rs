@@ -0,0 +1,301 @@
+use crate::catalog::items::{Recipe, WeightedIngredient};
+use crate::search::{find_substring_matches, parse_search_terms};
+use ratatui::crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+/// Step for the portion multiplier keys
+const FACTOR_STEP: f64 = 0.25;
+const MAX_FACTOR: f64 = 20.0;
+
+/// Which part of the screen receives key presses
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(super) enum Focus {
+    Recipes,
+    Search,
+    Ingredients,
+    Detail,
+}
+
+pub(super) struct App {
+    pub recipes: Vec<Recipe>,
+    pub query: String,
+    /// Indices into `recipes` of the recipes matching the query
+    pub matches: Vec<usize>,
+    /// Position in `matches`
+    pub selected: usize,
+    /// Position in the shown recipe's ingredients
+    pub selected_ingredient: usize,
+    /// Portion multiplier applied to the shown recipe
+    pub factor: f64,
+    pub focus: Focus,
+    pub quit: bool,
+}
+
+impl App {
+    pub fn new(recipes: Vec<Recipe>) -> Self {
+        let matches = (0..recipes.len()).collect();
+        App {
+            recipes,
+            query: String::new(),
+            matches,
+            selected: 0,
+            selected_ingredient: 0,
+            factor: 1.0,
+            focus: Focus::Recipes,
+            quit: false,
+        }
+    }
+
+    /// The highlighted recipe at its original size
+    pub fn selected_recipe(&self) -> Option<&Recipe> {
+        self.matches
+            .get(self.selected)
+            .map(|&index| &self.recipes[index])
+    }
+
+    /// The highlighted recipe scaled by the portion multiplier
+    pub fn shown_recipe(&self) -> Option<Recipe> {
+        self.selected_recipe()
+            .map(|recipe| recipe.scaled(self.factor))
+    }
+
+    /// The highlighted ingredient of the shown recipe, at its scaled amount
+    pub fn shown_ingredient(&self) -> Option<WeightedIngredient> {
+        self.shown_recipe()?
+            .ingredients
+            .get(self.selected_ingredient)
+            .cloned()
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent) {
+        if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c') {
+            self.quit = true;
+            return;
+        }
+        match self.focus {
+            Focus::Search => self.handle_search_key(key.code),
+            Focus::Recipes => self.handle_recipes_key(key.code),
+            Focus::Ingredients => self.handle_ingredients_key(key.code),
+            Focus::Detail => self.handle_detail_key(key.code),
+        }
+    }
+
+    fn handle_search_key(&mut self, code: KeyCode) {
+        match code {
+            KeyCode::Char(c) => {
+                self.query.push(c);
+                self.refilter();
+            }
+            KeyCode::Backspace => {
+                self.query.pop();
+                self.refilter();
+            }
+            KeyCode::Esc => {
+                self.query.clear();
+                self.refilter();
+                self.focus = Focus::Recipes;
+            }
+            KeyCode::Enter => self.focus = Focus::Recipes,
+            KeyCode::Down => self.move_recipe(1),
+            KeyCode::Up => self.move_recipe(-1),
+            _ => {}
+        }
+    }
+
+    fn handle_recipes_key(&mut self, code: KeyCode) {
+        match code {
+            KeyCode::Char('q') => self.quit = true,
+            KeyCode::Esc if !self.query.is_empty() => {
+                self.query.clear();
+                self.refilter();
+            }
+            KeyCode::Esc => self.quit = true,
+            KeyCode::Char('/') => self.focus = Focus::Search,
+            KeyCode::Down | KeyCode::Char('j') => self.move_recipe(1),
+            KeyCode::Up | KeyCode::Char('k') => self.move_recipe(-1),
+            KeyCode::Home | KeyCode::Char('g') => self.select_recipe(0),
+            KeyCode::End | KeyCode::Char('G') => {
+                self.select_recipe(self.matches.len().saturating_sub(1))
+            }
+            KeyCode::Enter | KeyCode::Tab | KeyCode::Right | KeyCode::Char('l') => {
+                if self
+                    .selected_recipe()
+                    .is_some_and(|recipe| !recipe.ingredients.is_empty())
+                {
+                    self.focus = Focus::Ingredients;
+                }
+            }
+            code => self.handle_factor_key(code),
+        }
+    }
+
+    fn handle_ingredients_key(&mut self, code: KeyCode) {
+        let count = self
+            .selected_recipe()
+            .map_or(0, |recipe| recipe.ingredients.len());
+        match code {
+            KeyCode::Char('q') => self.quit = true,
+            KeyCode::Esc | KeyCode::Tab | KeyCode::Left | KeyCode::Char('h') => {
+                self.focus = Focus::Recipes
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                self.selected_ingredient =
+                    (self.selected_ingredient + 1).min(count.saturating_sub(1))
+            }
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.selected_ingredient = self.selected_ingredient.saturating_sub(1)
+            }
+            KeyCode::Enter => self.focus = Focus::Detail,
+            code => self.handle_factor_key(code),
+        }
+    }
+
+    fn handle_detail_key(&mut self, code: KeyCode) {
+        match code {
+            KeyCode::Char('q') => self.quit = true,
+            KeyCode::Esc
+            | KeyCode::Enter
+            | KeyCode::Backspace
+            | KeyCode::Left
+            | KeyCode::Char('h') => self.focus = Focus::Ingredients,
+            code => self.handle_factor_key(code),
+        }
+    }
+
+    /// `+`/`-` change the portion multiplier by a quarter, `0` resets it
+    fn handle_factor_key(&mut self, code: KeyCode) {
+        match code {
+            KeyCode::Char('+') | KeyCode::Char('=') => {
+                self.factor = (self.factor + FACTOR_STEP).min(MAX_FACTOR)
+            }
+            KeyCode::Char('-') => self.factor = (self.factor - FACTOR_STEP).max(FACTOR_STEP),
+            KeyCode::Char('0') => self.factor = 1.0,
+            _ => {}
+        }
+    }
+
+    fn move_recipe(&mut self, delta: isize) {
+        let last = self.matches.len().saturating_sub(1);
+        self.select_recipe(self.selected.saturating_add_signed(delta).min(last));
+    }
+
+    fn select_recipe(&mut self, position: usize) {
+        if position != self.selected {
+            self.selected = position;
+            self.selected_ingredient = 0;
+        }
+    }
+
+    /// Match recipes against the query, keeping the highlighted recipe when it still matches
+    fn refilter(&mut self) {
+        let current = self.matches.get(self.selected).copied();
+        let found = find_substring_matches(&self.recipes, &parse_search_terms(&self.query));
+        self.matches = (0..self.recipes.len())
+            .filter(|&index| {
+                found
+                    .iter()
+                    .any(|recipe| std::ptr::eq(*recipe, &self.recipes[index]))
+            })
+            .collect();
+        let position = current
+            .and_then(|index| self.matches.iter().position(|&i| i == index))
+            .unwrap_or(0);
+        if current != self.matches.get(position).copied() {
+            self.selected_ingredient = 0;
+        }
+        self.selected = position;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::catalog::items::Ingredient;
+
+    fn recipe(name: &str, ingredient_ids: &[&str]) -> Recipe {
+        Recipe {
+            name: name.to_string(),
+            tags: Vec::new(),
+            cooked_grams: None,
+            ingredients: ingredient_ids
+                .iter()
+                .map(|id| WeightedIngredient {
+                    grams: 100.0,
+                    ingredient: Ingredient {
+                        id: id.to_string(),
+                        name: id.to_string(),
+                        carbs_per_100g: 10.0,
+                        protein_per_100g: 5.0,
+                        fat_per_100g: 2.0,
+                        fiber_per_100g: 1.0,
+                        aisle: None,
+                        package_grams: None,
+                    },
+                })
+                .collect(),
+        }
+    }
+
+    fn app() -> App {
+        App::new(vec![
+            recipe("Chicken Rice Bowl", &["chicken", "rice"]),
+            recipe("Porridge", &["oats"]),
+            recipe("Chicken Salad", &["chicken", "lettuce", "oil"]),
+        ])
+    }
+
+    fn press(app: &mut App, keys: &str) {
+        for c in keys.chars() {
+            app.handle_key(KeyEvent::from(KeyCode::Char(c)));
+        }
+    }
+
+    #[test]
+    fn test_search_filters_incrementally_and_keeps_selection() {
+        let mut app = app();
+        press(&mut app, "jj/chick");
+        assert_eq!(app.matches, vec![0, 2]);
+        assert_eq!(app.selected_recipe().unwrap().name, "Chicken Salad");
+
+        press(&mut app, "en sal");
+        assert_eq!(app.matches, vec![2]);
+
+        app.handle_key(KeyEvent::from(KeyCode::Esc));
+        assert_eq!(app.matches, vec![0, 1, 2]);
+        assert_eq!(app.focus, Focus::Recipes);
+        assert_eq!(app.selected_recipe().unwrap().name, "Chicken Salad");
+    }
+
+    #[test]
+    fn test_factor_keys_scale_the_shown_recipe() {
+        let mut app = app();
+        press(&mut app, "++");
+        assert_eq!(app.shown_recipe().unwrap().ingredients[0].grams, 150.0);
+
+        press(&mut app, "------");
+        assert_eq!(app.factor, FACTOR_STEP);
+
+        press(&mut app, "0");
+        assert_eq!(app.factor, 1.0);
+    }
+
+    #[test]
+    fn test_ingredient_detail_navigation() {
+        let mut app = app();
+        press(&mut app, "G");
+        app.handle_key(KeyEvent::from(KeyCode::Enter));
+        press(&mut app, "jjj");
+        assert_eq!(app.focus, Focus::Ingredients);
+        assert_eq!(app.shown_ingredient().unwrap().ingredient.id, "oil");
+
+        app.handle_key(KeyEvent::from(KeyCode::Enter));
+        assert_eq!(app.focus, Focus::Detail);
+        app.handle_key(KeyEvent::from(KeyCode::Esc));
+        app.handle_key(KeyEvent::from(KeyCode::Esc));
+        assert_eq!(app.focus, Focus::Recipes);
+
+        press(&mut app, "k");
+        assert_eq!(app.selected_ingredient, 0);
+        press(&mut app, "q");
+        assert!(app.quit);
+    }
+}
@@ -0,0 +1,39 @@
+mod app;
+mod ui;
+
+use crate::catalog;
+use crate::error::{AppError, AppResult};
+use app::App;
+use ratatui::DefaultTerminal;
+use ratatui::crossterm::event::{self, Event, KeyEventKind};
+use std::io::{self, IsTerminal};
+
+/// Full-screen recipe browser
+///
+/// Draws with plain terminal escape codes, so it works in any terminal,
+/// including over SSH.
+pub fn run() -> AppResult<()> {
+    if !io::stdin().is_terminal() || !io::stdout().is_terminal() {
+        return Err(AppError::NotATerminal {
+            command: "tui".to_string(),
+        });
+    }
+    let mut app = App::new(catalog::load_recipes()?);
+
+    let mut terminal = ratatui::try_init()?;
+    let result = event_loop(&mut terminal, &mut app);
+    ratatui::restore();
+    result
+}
+
+fn event_loop(terminal: &mut DefaultTerminal, app: &mut App) -> AppResult<()> {
+    while !app.quit {
+        terminal.draw(|frame| ui::draw(frame, app))?;
+        if let Event::Key(key) = event::read()?
+            && key.kind == KeyEventKind::Press
+        {
+            app.handle_key(key);
+        }
+    }
+    Ok(())
+}
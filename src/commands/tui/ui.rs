@@ -0,0 +1,247 @@
+use super::app::{App, Focus};
+use crate::commands::display::{
+    format_calories, format_energy_split, format_number_with_unit, nutrition_table_cells,
+};
+use crate::commands::ingredient::find_usages;
+use ratatui::Frame;
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{
+    Block, Borders, Clear, List, ListItem, ListState, Paragraph, Row, Table, TableState, Wrap,
+};
+
+const RECIPE_LIST_WIDTH: u16 = 32;
+
+/// Only the basic ANSI colors, so the screen looks the same over SSH and on plain consoles
+const ACCENT: Color = Color::Cyan;
+const HIGHLIGHT: Color = Color::Yellow;
+
+pub(super) fn draw(frame: &mut Frame, app: &App) {
+    let [main, status] =
+        Layout::vertical([Constraint::Min(0), Constraint::Length(1)]).areas(frame.area());
+    let [sidebar, content] =
+        Layout::horizontal([Constraint::Length(RECIPE_LIST_WIDTH), Constraint::Min(0)]).areas(main);
+    let [search, list] =
+        Layout::vertical([Constraint::Length(3), Constraint::Min(0)]).areas(sidebar);
+
+    draw_search(frame, app, search);
+    draw_recipe_list(frame, app, list);
+    draw_nutrition(frame, app, content);
+    if app.focus == Focus::Detail {
+        draw_ingredient_detail(frame, app, content);
+    }
+    draw_status(frame, app, status);
+}
+
+fn draw_search(frame: &mut Frame, app: &App, area: Rect) {
+    let searching = app.focus == Focus::Search;
+    let text = if searching {
+        Line::from(vec![Span::raw(&app.query), Span::styled("▏", focused())])
+    } else if app.query.is_empty() {
+        Line::styled(
+            "press / to search",
+            Style::new().add_modifier(Modifier::DIM),
+        )
+    } else {
+        Line::raw(&app.query)
+    };
+    frame.render_widget(Paragraph::new(text).block(panel("Search", searching)), area);
+}
+
+fn draw_recipe_list(frame: &mut Frame, app: &App, area: Rect) {
+    let title = format!("Recipes ({}/{})", app.matches.len(), app.recipes.len());
+    let items: Vec<ListItem> = app
+        .matches
+        .iter()
+        .map(|&index| ListItem::new(app.recipes[index].name.as_str()))
+        .collect();
+    let list = List::new(items)
+        .block(panel(
+            &title,
+            matches!(app.focus, Focus::Recipes | Focus::Search),
+        ))
+        .highlight_style(Style::new().fg(HIGHLIGHT).add_modifier(Modifier::BOLD))
+        .highlight_symbol("▶ ");
+    let mut state =
+        ListState::default().with_selected((!app.matches.is_empty()).then_some(app.selected));
+    frame.render_stateful_widget(list, area, &mut state);
+}
+
+fn draw_nutrition(frame: &mut Frame, app: &App, area: Rect) {
+    let focused_here = matches!(app.focus, Focus::Ingredients | Focus::Detail);
+    let Some(recipe) = app.shown_recipe() else {
+        let message = if app.recipes.is_empty() {
+            "The catalog has no recipes."
+        } else {
+            "No recipes match the search."
+        };
+        frame.render_widget(
+            Paragraph::new(message).block(panel("Nutrition", focused_here)),
+            area,
+        );
+        return;
+    };
+
+    let block = panel(&recipe.name, focused_here);
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+    let [summary, table_area, footer] = Layout::vertical([
+        Constraint::Length(2),
+        Constraint::Min(0),
+        Constraint::Length(2),
+    ])
+    .areas(inner);
+
+    let mut details = vec![Span::styled(
+        format!("Portion ×{:.2}", app.factor),
+        Style::new().fg(HIGHLIGHT),
+    )];
+    if let Some(cooked) = recipe.cooked_grams {
+        details.push(Span::raw(format!(
+            "  ·  {} cooked",
+            format_number_with_unit(cooked, "g")
+        )));
+    }
+    if !recipe.tags.is_empty() {
+        details.push(Span::raw(format!("  ·  {}", recipe.tags.join(", "))));
+    }
+    frame.render_widget(Paragraph::new(Line::from(details)), summary);
+
+    let (headers, cells) = nutrition_table_cells(&recipe.ingredients);
+    let total_row = cells.len() - 1;
+    let rows = cells.into_iter().enumerate().map(|(index, cells)| {
+        let row = Row::new(cells);
+        if index == total_row {
+            row.style(Style::new().add_modifier(Modifier::BOLD))
+        } else {
+            row
+        }
+    });
+    let mut widths = vec![Constraint::Fill(1)];
+    widths.extend(std::iter::repeat_n(
+        Constraint::Length(10),
+        headers.len() - 1,
+    ));
+    let table = Table::new(rows, widths)
+        .header(Row::new(headers).style(Style::new().fg(ACCENT)))
+        .row_highlight_style(Style::new().fg(HIGHLIGHT).add_modifier(Modifier::REVERSED))
+        .highlight_symbol("▶ ");
+    let mut state =
+        TableState::default().with_selected(focused_here.then_some(app.selected_ingredient));
+    frame.render_stateful_widget(table, table_area, &mut state);
+
+    if let Some(split) = recipe.totals().energy_split() {
+        frame.render_widget(Paragraph::new(format_energy_split(&split)), footer);
+    }
+}
+
+/// Popup over the nutrition pane with the ingredient's values per 100 g and its uses
+fn draw_ingredient_detail(frame: &mut Frame, app: &App, area: Rect) {
+    let Some(entry) = app.shown_ingredient() else {
+        return;
+    };
+    let ingredient = &entry.ingredient;
+    let per_100g = ingredient.nutrition_for(100.0);
+    let label = |text: &str| Span::styled(format!("{:<12}", text), Style::new().fg(ACCENT));
+
+    let mut lines = vec![
+        Line::from(vec![label("ID"), Span::raw(&ingredient.id)]),
+        Line::from(vec![
+            label("In recipe"),
+            Span::raw(format_number_with_unit(entry.grams, "g")),
+            Span::raw(format!(
+                " ({})",
+                format_calories(entry.nutrition().calories)
+            )),
+        ]),
+    ];
+    if let Some(aisle) = &ingredient.aisle {
+        lines.push(Line::from(vec![label("Aisle"), Span::raw(aisle)]));
+    }
+    if let Some(package) = ingredient.package_grams {
+        lines.push(Line::from(vec![
+            label("Package"),
+            Span::raw(format_number_with_unit(package, "g")),
+        ]));
+    }
+    lines.push(Line::raw(""));
+    lines.push(Line::styled(
+        "Per 100 g",
+        Style::new().add_modifier(Modifier::BOLD),
+    ));
+    for (name, value) in [
+        ("Net carbs", format_number_with_unit(per_100g.carbs, "g")),
+        ("Protein", format_number_with_unit(per_100g.protein, "g")),
+        ("Fat", format_number_with_unit(per_100g.fat, "g")),
+        ("Fiber", format_number_with_unit(per_100g.fiber, "g")),
+        ("Calories", format_calories(per_100g.calories)),
+    ] {
+        lines.push(Line::from(vec![label(name), Span::raw(value)]));
+    }
+    if let Some(split) = per_100g.energy_split() {
+        lines.push(Line::raw(format_energy_split(&split)));
+    }
+    lines.push(Line::raw(""));
+    let usages = find_usages(&app.recipes, &ingredient.id);
+    lines.push(Line::styled(
+        format!("Used in {} of {} recipes", usages.len(), app.recipes.len()),
+        Style::new().add_modifier(Modifier::BOLD),
+    ));
+    for (recipe, grams) in usages {
+        lines.push(Line::raw(format!(
+            "  {}: {}",
+            recipe.name,
+            format_number_with_unit(grams, "g")
+        )));
+    }
+
+    let popup = centered(area, 60, lines.len() as u16 + 2);
+    frame.render_widget(Clear, popup);
+    frame.render_widget(
+        Paragraph::new(lines)
+            .wrap(Wrap { trim: false })
+            .block(panel(&ingredient.name, true)),
+        popup,
+    );
+}
+
+fn draw_status(frame: &mut Frame, app: &App, area: Rect) {
+    let hints = match app.focus {
+        Focus::Recipes => "↑↓ move  / search  enter ingredients  +/- portion  0 reset  q quit",
+        Focus::Search => "type to filter  ↑↓ move  enter done  esc clear",
+        Focus::Ingredients => "↑↓ move  enter details  +/- portion  0 reset  esc back  q quit",
+        Focus::Detail => "esc back  +/- portion  q quit",
+    };
+    frame.render_widget(
+        Paragraph::new(hints).style(Style::new().add_modifier(Modifier::DIM)),
+        area,
+    );
+}
+
+fn panel(title: &str, focused_panel: bool) -> Block<'static> {
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(format!(" {} ", title));
+    if focused_panel {
+        block.border_style(focused())
+    } else {
+        block
+    }
+}
+
+fn focused() -> Style {
+    Style::new().fg(ACCENT)
+}
+
+/// Area of at most `width` × `height` in the middle of `area`
+fn centered(area: Rect, width: u16, height: u16) -> Rect {
+    let width = width.min(area.width);
+    let height = height.min(area.height);
+    Rect {
+        x: area.x + (area.width - width) / 2,
+        y: area.y + (area.height - height) / 2,
+        width,
+        height,
+    }
+}
@@ -0,0 +1,130 @@
+use crate::catalog::{self, Catalog};
+use crate::error::AppResult;
+use ratatui::crossterm::cursor::MoveTo;
+use ratatui::crossterm::execute;
+use ratatui::crossterm::terminal::{Clear, ClearType};
+use std::io::{self, IsTerminal};
+use std::path::PathBuf;
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+/// How often the catalog files are checked for saves
+const POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// The catalog files, plus any others a command reads, and their modification times
+/// when last looked at
+struct FileWatch {
+    files: Vec<PathBuf>,
+    seen: Vec<Option<SystemTime>>,
+}
+
+impl FileWatch {
+    fn new(extra_files: Vec<PathBuf>) -> AppResult<Self> {
+        let mut files = catalog::source_files()?;
+        files.extend(extra_files);
+        let seen = modification_times(&files);
+        Ok(FileWatch { files, seen })
+    }
+
+    /// Take note of the current modification times; `true` if any changed since last time
+    fn refresh(&mut self) -> bool {
+        let current = modification_times(&self.files);
+        let changed = current != self.seen;
+        self.seen = current;
+        changed
+    }
+
+    /// The file names as a list for a sentence, like "a, b and c"
+    fn names(&self) -> String {
+        let names: Vec<String> = self
+            .files
+            .iter()
+            .filter_map(|file| file.file_name())
+            .map(|name| name.to_string_lossy().into_owned())
+            .collect();
+        match names.split_last() {
+            Some((last, rest)) if !rest.is_empty() => format!("{} and {}", rest.join(", "), last),
+            _ => names.concat(),
+        }
+    }
+}
+
+/// The catalog, loaded once and again whenever its files change
+pub(crate) struct LiveCatalog {
+    pub(crate) catalog: Catalog,
+    files: FileWatch,
+    /// Incremented on every reload, so clients can tell they are out of date
+    pub(crate) generation: u64,
+}
+
+impl LiveCatalog {
+    pub(crate) fn load() -> AppResult<Self> {
+        let files = FileWatch::new(Vec::new())?;
+        Ok(LiveCatalog {
+            catalog: catalog::load()?,
+            files,
+            generation: 0,
+        })
+    }
+
+    pub(crate) fn reload(&mut self) -> AppResult<()> {
+        self.files.refresh();
+        self.catalog = catalog::load()?;
+        self.generation += 1;
+        Ok(())
+    }
+
+    /// Reload when a catalog file changed since the last load
+    ///
+    /// A catalog that fails to load is reported and the previous one kept.
+    pub(crate) fn refresh(&mut self) {
+        if !self.files.refresh() {
+            return;
+        }
+        match catalog::load() {
+            Ok(catalog) => {
+                self.catalog = catalog;
+                self.generation += 1;
+                println!("↻ Catalog files changed, reloaded");
+            }
+            Err(e) => eprintln!("Error: {}\n\nKeeping the previously loaded catalog.", e),
+        }
+    }
+}
+
+fn modification_times(files: &[PathBuf]) -> Vec<Option<SystemTime>> {
+    files
+        .iter()
+        .map(|file| file.metadata().and_then(|m| m.modified()).ok())
+        .collect()
+}
+
+/// Run `render` now and again after every save to the catalog files or to
+/// `extra_files`, the other files `render` reads, until interrupted
+///
+/// Errors from `render` are printed in place of its output and watching goes on,
+/// so a half-edited file only shows what is wrong with it until the next save.
+pub(crate) fn watch(
+    extra_files: Vec<PathBuf>,
+    mut render: impl FnMut() -> AppResult<()>,
+) -> AppResult<()> {
+    let mut files = FileWatch::new(extra_files)?;
+    loop {
+        if let Err(e) = render() {
+            println!("Error: {}", e);
+        }
+        println!("\nWatching {} for changes (Ctrl-C to stop)", files.names());
+        while !files.refresh() {
+            thread::sleep(POLL_INTERVAL);
+        }
+    }
+}
+
+/// Start the next redraw on an empty screen; output going elsewhere is left alone
+pub(crate) fn clear_screen() -> AppResult<()> {
+    let mut stdout = io::stdout();
+    if stdout.is_terminal() {
+        execute!(stdout, Clear(ClearType::All), MoveTo(0, 0))?;
+    }
+    Ok(())
+}
@@ -1,50 +1,180 @@
 use std::path::PathBuf;
 
+/// Entries of a catalog file sharing a key that must be unique
 #[derive(Debug)]
 pub struct DuplicateGroup {
+    /// The repeated key, e.g. an ingredient ID
     pub key: String,
+    /// Display names of the entries using it
     pub items: Vec<String>,
 }
 
+/// Everything that can go wrong, with a message meant for the user
+///
+/// The `Display` output is complete, including tips on how to fix the problem.
 // Errors not specific to the storage format
 #[derive(Debug)]
 pub enum AppError {
+    /// No catalog in the current directory or any parent
     CatalogNotFound {
+        /// Directories looked in, nearest first
         searched: Vec<PathBuf>,
+        /// Full message including the searched directories
         message: String,
     },
+    /// `init` refused to write into a directory with files in it
     DirectoryNotEmpty {
+        /// The directory
         path: PathBuf,
+        /// Full message
         message: String,
     },
+    /// A recipe refers to an ingredient ID that is not defined
     UnknownIngredient {
+        /// Recipe name
         recipe: String,
+        /// The unknown ID
         ingredient: String,
+        /// Closest defined ID, if any is close
         suggestion: Option<String>,
+        /// All defined IDs
         available_ids: Vec<String>,
     },
+    /// A meal plan refers to a recipe that is not defined
+    UnknownRecipe {
+        /// Day name
+        day: String,
+        /// The unknown recipe name
+        recipe: String,
+        /// Closest recipe name, if any is close
+        suggestion: Option<String>,
+    },
+    /// Targets were asked for a person not in targets.jsonc
+    UnknownPerson {
+        /// The unknown person
+        person: String,
+        /// Closest person, if any is close
+        suggestion: Option<String>,
+        /// People with targets in targets.jsonc
+        available: Vec<String>,
+    },
+    /// The catalog has no plans.jsonc
+    PlansNotFound {
+        /// Where the file was expected
+        path: PathBuf,
+    },
+    /// No single meal plan day matches the query
+    DayNotFound {
+        /// What was searched for
+        query: String,
+        /// Days matching the query when it is ambiguous, otherwise all days
+        candidates: Vec<String>,
+    },
+    /// No single recipe or ingredient matches the query
+    FoodNotFound {
+        /// What was searched for
+        query: String,
+        /// Recipe names and ingredient IDs matching the query when it is ambiguous
+        candidates: Vec<String>,
+    },
+    /// No single ingredient matches the query
+    IngredientNotFound {
+        /// What was searched for
+        query: String,
+        /// Ingredient IDs matching the query when it is ambiguous
+        candidates: Vec<String>,
+    },
+    /// The pantry lists an ingredient ID that is not defined
+    UnknownPantryIngredient {
+        /// The unknown ID
+        ingredient: String,
+        /// Closest defined ID, if any is close
+        suggestion: Option<String>,
+    },
+    /// Cooking a recipe needs more than the pantry has
+    InsufficientStock {
+        /// Recipe name
+        recipe: String,
+        /// Missing amounts, e.g. "30.0 g Brown Rice (cooked)"
+        shortfalls: Vec<String>,
+    },
+    /// No single recipe matches the query
+    RecipeNotFound {
+        /// What was searched for
+        query: String,
+        /// Recipes matching the query when it is ambiguous
+        candidates: Vec<String>,
+    },
+    /// A catalog file defines the same key more than once
     DuplicateKey {
+        /// File name, e.g. "recipes.jsonc"
         filename: String,
+        /// What the key is, e.g. "recipe name"
         key_type: String,
+        /// Each repeated key with its entries
         duplicates: Vec<DuplicateGroup>,
     },
+    /// A catalog file could not be read
     FileUnreadable {
+        /// The file
         path: PathBuf,
+        /// The underlying I/O error, as text
         io_error: String,
     },
+    /// A catalog file is not valid JSONC
     ParsingError {
+        /// Full message, with the file and position
         message: String,
     },
+    /// A catalog file does not match its JSON schema
     SchemaComplianceError {
+        /// Full message, with the offending entries
         message: String,
     },
+    /// A bundled JSON schema could not be parsed
     InvalidSchema {
+        /// Full message
         message: String,
     },
+    /// Valid JSON could not be turned into catalog items
     TypeMappingError {
+        /// Full message
+        message: String,
+    },
+    /// An edit to the catalog files is not possible
+    InvalidEdit {
+        /// Full message
+        message: String,
+    },
+    /// `fmt --check` found files that are not formatted
+    FormatCheckFailed {
+        /// Names of the files that would change
+        files: Vec<String>,
+    },
+    /// No amounts satisfy the requested macro targets
+    OptimizationFailed {
+        /// Full message
+        message: String,
+    },
+    /// A command-line value could not be used
+    InvalidArgument {
+        /// Full message
         message: String,
     },
+    /// A recipe cannot be scaled to a target, e.g. one without protein to 40 g protein
+    CannotScale {
+        /// Recipe name
+        recipe: String,
+        /// Requested target, e.g. "600 kcal"
+        target: String,
+    },
+    /// An interactive command was run with input or output redirected
+    NotATerminal {
+        /// Command needing the terminal, e.g. "tui"
+        command: String,
+    },
 
+    /// Any other I/O failure
     Io(std::io::Error),
 }
 
@@ -56,7 +186,10 @@ impl std::fmt::Display for AppError {
             | AppError::ParsingError { message, .. }
             | AppError::SchemaComplianceError { message, .. }
             | AppError::InvalidSchema { message, .. }
-            | AppError::TypeMappingError { message, .. } => write!(f, "{}", message),
+            | AppError::TypeMappingError { message, .. }
+            | AppError::InvalidEdit { message, .. }
+            | AppError::OptimizationFailed { message, .. }
+            | AppError::InvalidArgument { message, .. } => write!(f, "{}", message),
 
             AppError::FileUnreadable { path, io_error } => {
                 write!(f, "Cannot read file {}: {}", path.display(), io_error)
@@ -92,6 +225,125 @@ impl std::fmt::Display for AppError {
                 )
             }
 
+            AppError::UnknownRecipe {
+                day,
+                recipe,
+                suggestion,
+            } => {
+                write!(f, "Day '{}' references unknown recipe '{}'", day, recipe)?;
+
+                if let Some(suggested) = suggestion {
+                    write!(f, ".\n\nDid you mean '{}'?", suggested)?;
+                }
+
+                write!(
+                    f,
+                    "\n\nTip: Fix recipe references in plans.jsonc before running commands."
+                )
+            }
+
+            AppError::UnknownPerson {
+                person,
+                suggestion,
+                available,
+            } => {
+                write!(f, "No targets for '{}' in targets.jsonc", person)?;
+
+                if let Some(suggested) = suggestion {
+                    write!(f, ".\n\nDid you mean '{}'?", suggested)?;
+                }
+
+                if available.is_empty() {
+                    write!(
+                        f,
+                        "\n\nTip: Add the person under \"people\" in targets.jsonc."
+                    )
+                } else {
+                    write!(f, "\n\nPeople with targets: {}", available.join(", "))
+                }
+            }
+
+            AppError::PlansNotFound { path } => write!(
+                f,
+                "No meal plans found at {}\n\nTip: Create plans.jsonc next to recipes.jsonc. Running 'nutriterm init' in an empty directory shows an example.",
+                path.display()
+            ),
+
+            AppError::DayNotFound { query, candidates } => {
+                if candidates.is_empty() {
+                    write!(f, "No day matches '{}' (plans.jsonc has no days)", query)
+                } else {
+                    write!(
+                        f,
+                        "No single day matches '{}'. Days: {}",
+                        query,
+                        candidates.join(", ")
+                    )
+                }
+            }
+
+            AppError::FoodNotFound { query, candidates } => {
+                if candidates.is_empty() {
+                    write!(f, "No recipe or ingredient matches '{}'", query)
+                } else {
+                    write!(
+                        f,
+                        "Multiple recipes or ingredients match '{}': {}\n\nPlease be more specific with your search term.",
+                        query,
+                        candidates.join(", ")
+                    )
+                }
+            }
+
+            AppError::IngredientNotFound { query, candidates } => {
+                if candidates.is_empty() {
+                    write!(f, "No ingredient matches '{}'", query)
+                } else {
+                    write!(
+                        f,
+                        "Multiple ingredients match '{}': {}\n\nPlease be more specific with your search term.",
+                        query,
+                        candidates.join(", ")
+                    )
+                }
+            }
+
+            AppError::UnknownPantryIngredient {
+                ingredient,
+                suggestion,
+            } => {
+                write!(f, "pantry.jsonc lists unknown ingredient '{}'", ingredient)?;
+
+                if let Some(suggested) = suggestion {
+                    write!(f, ".\n\nDid you mean '{}'?", suggested)?;
+                }
+                Ok(())
+            }
+
+            AppError::InsufficientStock { recipe, shortfalls } => write!(
+                f,
+                "Not enough in the pantry to cook '{}'. Missing:\n{}\n\nTip: Add stock with 'nutriterm pantry add', or use --force to use up what is there.",
+                recipe,
+                shortfalls
+                    .iter()
+                    .map(|s| format!("  - {}", s))
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            ),
+
+            AppError::RecipeNotFound { query, candidates } => {
+                if candidates.is_empty() {
+                    write!(f, "No recipe matches '{}'", query)
+                } else {
+                    write!(
+                        f,
+                        "Multiple recipes match '{}': {}\n\nPlease be more specific with your search term.",
+                        query,
+                        candidates.join(", ")
+                    )
+                }
+            }
+
             AppError::DuplicateKey {
                 filename,
                 key_type,
@@ -112,6 +364,24 @@ impl std::fmt::Display for AppError {
                 )
             }
 
+            AppError::CannotScale { recipe, target } => write!(
+                f,
+                "Cannot scale '{}' to {}: the recipe currently has none",
+                recipe, target
+            ),
+
+            AppError::NotATerminal { command } => write!(
+                f,
+                "'nutriterm {}' needs an interactive terminal, but input or output is redirected",
+                command
+            ),
+
+            AppError::FormatCheckFailed { files } => write!(
+                f,
+                "Catalog files are not formatted: {}\n\nTip: Run 'nutriterm fmt' to rewrite them in the canonical layout.",
+                files.join(", ")
+            ),
+
             // Legacy variants
             AppError::Io(error) => write!(f, "{}", error),
         }
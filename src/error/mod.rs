@@ -1,5 +1,8 @@
+//! The error type shared by the library and the command line
+
 mod app_error;
 
 pub use app_error::{AppError, DuplicateGroup};
 
+/// Result of library and command operations
 pub type AppResult<T> = Result<T, AppError>;
@@ -0,0 +1,43 @@
+//! Nutrition calculations for the recipes and ingredients of a nutriterm catalog
+//!
+//! This is the library behind the `nutriterm` command. It loads catalogs, finds
+//! recipes and ingredients by name or search terms, and computes nutrition for
+//! weighed ingredients and whole recipes. Nothing in it prints; problems come back
+//! as [`AppError`], whose `Display` output is the message the command line shows.
+//!
+//! ```
+//! use nutriterm::{catalog, Nutrition};
+//!
+//! # let dir = tempfile::tempdir()?;
+//! # catalog::initialize(dir.path())?;
+//! // catalog::load() finds the catalog from the current directory instead
+//! let catalog = catalog::load_from(dir.path())?;
+//!
+//! let recipe = catalog.recipe("chicken rice")?;
+//! let totals: Nutrition = recipe.totals();
+//! println!("{}: {:.0} kcal, {:.1} g protein", recipe.name, totals.calories, totals.protein);
+//!
+//! let rice = catalog.ingredient("brown_rice")?;
+//! let portion = rice.nutrition_for(150.0);
+//! assert_eq!(portion.grams, 150.0);
+//!
+//! let doubled = recipe.scaled(2.0);
+//! assert!((doubled.totals().calories - 2.0 * totals.calories).abs() < 1e-9);
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+//!
+//! The crate is not 1.0 yet, so any release may change this API. The command-line
+//! interface itself lives in the binary, and `utils` only holds helpers shared with
+//! it, which is why it is left out of these docs.
+
+#![warn(missing_docs)]
+
+pub mod catalog;
+pub mod error;
+pub mod search;
+#[doc(hidden)]
+pub mod utils;
+
+pub use catalog::Catalog;
+pub use catalog::items::{Ingredient, Nutrition, Recipe, WeightedIngredient};
+pub use error::{AppError, AppResult};
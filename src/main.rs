@@ -1,9 +1,9 @@
-pub mod catalog;
-pub mod commands;
-pub mod error;
-pub mod utils;
+mod commands;
 
-use clap::{Parser, Subcommand};
+use nutriterm::{catalog, error, search, utils};
+
+use chrono::NaiveDate;
+use clap::{CommandFactory, Parser, Subcommand};
 use error::AppResult;
 
 #[derive(Parser)]
@@ -18,16 +18,110 @@ struct Cli {
 enum Commands {
     #[command(about = "Initialize current directory as a recipe catalog")]
     Init,
-    #[command(about = "Display nutrition for a specific recipe")]
+    #[command(
+        about = "Display nutrition for a specific recipe",
+        args_conflicts_with_subcommands = true,
+        subcommand_negates_reqs = true
+    )]
     Recipe {
-        #[arg(help = "Recipe name (e.g., chicken-rice-bowl)")]
-        name: String,
+        #[command(subcommand)]
+        command: Option<commands::recipe::RecipeCommand>,
+        #[arg(
+            required = true,
+            add = commands::completions::recipe_names(),
+            help = "Recipe name (e.g., chicken-rice-bowl)"
+        )]
+        name: Option<String>,
+        #[command(flatten)]
+        scale: commands::recipe::ScaleArgs,
+        #[command(flatten)]
+        display: commands::recipe::DisplayArgs,
+        #[arg(
+            long,
+            help = "Show the recipe again whenever the catalog files are saved"
+        )]
+        watch: bool,
+    },
+    #[command(
+        about = "Show nutrition for an ingredient and the recipes using it",
+        args_conflicts_with_subcommands = true,
+        subcommand_negates_reqs = true
+    )]
+    Ingredient {
+        #[command(subcommand)]
+        command: Option<commands::ingredient::IngredientCommand>,
+        #[arg(
+            required = true,
+            add = commands::completions::ingredient_ids(),
+            help = "Ingredient ID or name (e.g., brown_rice)"
+        )]
+        query: Option<String>,
+    },
+    #[command(about = "Compare the nutrition of two or more recipes side by side")]
+    Compare {
+        #[arg(
+            required = true,
+            num_args = 2..,
+            add = commands::completions::recipe_names(),
+            help = "Recipe names or search terms (the first is the baseline)"
+        )]
+        recipes: Vec<String>,
+    },
+    #[command(about = "Adjust a recipe's amounts as little as possible to meet nutrition targets")]
+    Optimize(commands::optimize::OptimizeArgs),
+    #[command(about = "Show per-meal and whole-day nutrition for a day in plans.jsonc")]
+    Plan {
+        #[arg(help = "Day name or part of it (e.g., monday)")]
+        day: String,
+        #[arg(long, help = "Compare against this person's daily targets")]
+        person: Option<String>,
+    },
+    #[command(about = "Add up the ingredients of several recipes into a shopping list")]
+    Shop(commands::shop::ShopArgs),
+    #[command(about = "Show pantry stock, low items and which recipes you can cook")]
+    Pantry {
+        #[command(subcommand)]
+        command: Option<commands::pantry::PantryCommand>,
+    },
+    #[command(about = "Cook a recipe, deducting its ingredients from the pantry")]
+    Cook(commands::pantry::CookArgs),
+    #[command(about = "Record a recipe or ingredient you ate in the food diary")]
+    Log(commands::diary::LogArgs),
+    #[command(about = "Show the food diary and totals for a day")]
+    Day {
+        #[arg(
+            value_parser = commands::diary::parse_date,
+            help = "YYYY-MM-DD, today or yesterday [default: today]"
+        )]
+        date: Option<NaiveDate>,
+        #[arg(long, help = "Compare against this person's daily targets")]
+        person: Option<String>,
     },
-    #[command(about = "Generate kitchen reference with all recipes in HTML format")]
-    KitchenRef,
+    #[command(about = "Show daily totals and averages from the food diary")]
+    Report(commands::diary::ReportArgs),
+    #[command(about = "List recipes with their nutrition totals")]
+    List(commands::list::ListArgs),
+    #[command(about = "Rewrite the catalog files in the canonical layout, keeping comments")]
+    Fmt(commands::fmt::FmtArgs),
+    #[command(about = "Generate kitchen reference with all or selected recipes in HTML format")]
+    KitchenRef(commands::kitchen_ref::KitchenRefArgs),
+    #[command(about = "Browse recipes and their nutrition in a full-screen terminal UI")]
+    Tui,
+    #[command(about = "Interactive prompt that loads the catalog once for repeated queries")]
+    Shell,
+    #[command(
+        about = "Print a shell completion script (completes recipe names and ingredient IDs)"
+    )]
+    Completions(commands::completions::CompletionsArgs),
+    #[command(
+        about = "Serve the kitchen reference and a JSON API over HTTP, reloading on file changes"
+    )]
+    Serve(commands::serve::ServeArgs),
 }
 
 fn main() {
+    commands::completions::answer_callback(Cli::command);
+
     if let Err(e) = run_app() {
         eprintln!("Error: {}", e);
         std::process::exit(1);
@@ -41,11 +135,70 @@ fn run_app() -> AppResult<()> {
         Commands::Init => {
             commands::init::run()?;
         }
-        Commands::Recipe { name } => {
-            commands::recipe::run(name)?;
+        Commands::Recipe {
+            command,
+            name,
+            scale,
+            display,
+            watch,
+        } => match (command, name) {
+            (Some(command), _) => commands::recipe::run_edit(command)?,
+            (None, Some(name)) if *watch => commands::recipe::run_watch(name, scale, display)?,
+            (None, Some(name)) => commands::recipe::run(name, scale, display)?,
+            (None, None) => unreachable!("clap requires a recipe name"),
+        },
+        Commands::Ingredient { command, query } => match (command, query) {
+            (Some(command), _) => commands::ingredient::run_edit(command)?,
+            (None, Some(query)) => commands::ingredient::run(query)?,
+            (None, None) => unreachable!("clap requires an ingredient query"),
+        },
+        Commands::Compare { recipes } => {
+            commands::compare::run(recipes)?;
+        }
+        Commands::Optimize(args) => {
+            commands::optimize::run(args)?;
+        }
+        Commands::Plan { day, person } => {
+            commands::plan::run(day, person.as_deref())?;
+        }
+        Commands::Shop(args) => {
+            commands::shop::run(args)?;
+        }
+        Commands::Pantry { command } => {
+            commands::pantry::run(command.as_ref())?;
+        }
+        Commands::Cook(args) => {
+            commands::pantry::cook(args)?;
+        }
+        Commands::Log(args) => {
+            commands::diary::log(args)?;
+        }
+        Commands::Day { date, person } => {
+            commands::diary::day(*date, person.as_deref())?;
+        }
+        Commands::Report(args) => {
+            commands::diary::report(args)?;
+        }
+        Commands::List(args) => {
+            commands::list::run(args)?;
+        }
+        Commands::Fmt(args) => {
+            commands::fmt::run(args)?;
+        }
+        Commands::KitchenRef(args) => {
+            commands::kitchen_ref::run(args)?;
+        }
+        Commands::Tui => {
+            commands::tui::run()?;
+        }
+        Commands::Shell => {
+            commands::shell::run()?;
+        }
+        Commands::Completions(args) => {
+            commands::completions::run(args)?;
         }
-        Commands::KitchenRef => {
-            commands::kitchen_ref::run()?;
+        Commands::Serve(args) => {
+            commands::serve::run(args)?;
         }
     }
     Ok(())
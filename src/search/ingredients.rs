@@ -0,0 +1,54 @@
+//! Finding ingredients by ID, name or search terms
+
+use crate::catalog::items::Ingredient;
+use crate::error::{AppError, AppResult};
+
+/// The ingredient with exactly this ID or name (case-insensitive)
+pub fn find_exact_match<'a>(ingredients: &'a [Ingredient], query: &str) -> Option<&'a Ingredient> {
+    ingredients
+        .iter()
+        .find(|i| i.id.eq_ignore_ascii_case(query) || i.name.eq_ignore_ascii_case(query))
+}
+
+/// Ingredients whose ID or name contains all search terms (case-insensitive)
+///
+/// Underscores in IDs count as spaces, so "brown rice" finds `brown_rice`.
+pub fn find_substring_matches<'a>(
+    ingredients: &'a [Ingredient],
+    query: &str,
+) -> Vec<&'a Ingredient> {
+    let terms: Vec<String> = query
+        .split(|c: char| c.is_whitespace() || c == '_')
+        .filter(|term| !term.is_empty())
+        .map(str::to_lowercase)
+        .collect();
+
+    ingredients
+        .iter()
+        .filter(|ingredient| {
+            let haystack =
+                format!("{} {}", ingredient.id.replace('_', " "), ingredient.name).to_lowercase();
+            terms.iter().all(|term| haystack.contains(term))
+        })
+        .collect()
+}
+
+/// Exact ingredient ID or name, otherwise the only ingredient matching
+///
+/// Fails with [`AppError::IngredientNotFound`] when nothing matches or the search
+/// is ambiguous; the error lists the candidate IDs in the latter case.
+pub fn resolve_ingredient<'a>(
+    ingredients: &'a [Ingredient],
+    query: &str,
+) -> AppResult<&'a Ingredient> {
+    if let Some(ingredient) = find_exact_match(ingredients, query) {
+        return Ok(ingredient);
+    }
+    match find_substring_matches(ingredients, query).as_slice() {
+        [ingredient] => Ok(ingredient),
+        matches => Err(AppError::IngredientNotFound {
+            query: query.to_string(),
+            candidates: matches.iter().map(|i| i.id.clone()).collect(),
+        }),
+    }
+}
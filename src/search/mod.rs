@@ -0,0 +1,51 @@
+//! Finding recipes and ingredients by name or search terms
+//!
+//! Every lookup first tries an exact match and then falls back to search terms
+//! that must all occur in the name, ignoring case.
+
+pub mod ingredients;
+
+use crate::catalog::items::Recipe;
+use crate::error::{AppError, AppResult};
+
+/// The recipe with exactly this name
+pub fn find_exact_match<'a>(recipes: &'a [Recipe], name: &str) -> Option<&'a Recipe> {
+    recipes.iter().find(|r| r.name == name)
+}
+
+/// Recipes whose name contains every search term (case-insensitive)
+pub fn find_substring_matches<'a>(recipes: &'a [Recipe], search_terms: &[&str]) -> Vec<&'a Recipe> {
+    recipes
+        .iter()
+        .filter(|recipe| {
+            let recipe_name_lower = recipe.name.to_lowercase();
+            search_terms
+                .iter()
+                .all(|term| recipe_name_lower.contains(&term.to_lowercase()))
+        })
+        .collect()
+}
+
+/// Split a query into the terms [`find_substring_matches`] looks for
+pub fn parse_search_terms(input: &str) -> Vec<&str> {
+    input.split_whitespace().collect()
+}
+
+/// Resolve an exact name or search terms to exactly one recipe
+///
+/// Fails with [`AppError::RecipeNotFound`] when nothing matches or the search is
+/// ambiguous; the error lists the candidates in the latter case.
+pub fn resolve_recipe<'a>(recipes: &'a [Recipe], query: &str) -> AppResult<&'a Recipe> {
+    if let Some(recipe) = find_exact_match(recipes, query) {
+        return Ok(recipe);
+    }
+
+    let matches = find_substring_matches(recipes, &parse_search_terms(query));
+    match matches.as_slice() {
+        [recipe] => Ok(recipe),
+        _ => Err(AppError::RecipeNotFound {
+            query: query.to_string(),
+            candidates: matches.iter().map(|r| r.name.clone()).collect(),
+        }),
+    }
+}
@@ -0,0 +1,256 @@
+//! Smallest changes to a set of values that satisfy linear constraints
+
+/// Linear constraint `min <= coefficients · x <= max` (bounds may be infinite)
+#[derive(Debug, Clone)]
+pub struct LinearConstraint {
+    /// One coefficient per value
+    pub coefficients: Vec<f64>,
+    /// Lower bound of the weighted sum
+    pub min: f64,
+    /// Upper bound of the weighted sum
+    pub max: f64,
+}
+
+/// Find values closest to `start` that satisfy the bounds and constraints
+#[derive(Debug, Clone)]
+pub struct LeastChangeProblem {
+    /// Values to change as little as possible
+    pub start: Vec<f64>,
+    /// Lower and upper bound for each value
+    pub bounds: Vec<(f64, f64)>,
+    /// Constraints the solution has to satisfy
+    pub constraints: Vec<LinearConstraint>,
+}
+
+const MAX_ITERATIONS: usize = 200_000;
+/// Convergence tolerance on the normalized constraints
+const TOLERANCE: f64 = 1e-9;
+/// Largest normalized constraint violation accepted in the final result
+const FEASIBILITY_TOLERANCE: f64 = 1e-5;
+
+/// A normalized one-sided (`c · y <= b`) or equality (`c · y == b`) row
+struct Row {
+    c: Vec<f64>,
+    b: f64,
+    equality: bool,
+}
+
+/// Solve the problem, returning `None` when no values satisfy every constraint
+///
+/// Changes are measured relative to the starting value (values below 1 count
+/// as 1), so the solution minimizes the sum of squared relative changes. This
+/// is a convex quadratic program; it is solved through its dual with an
+/// accelerated projected gradient method, which only needs a few
+/// multiplications per constraint and iteration.
+pub fn solve(problem: &LeastChangeProblem) -> Option<Vec<f64>> {
+    let start = &problem.start;
+    let scale: Vec<f64> = start.iter().map(|s| s.abs().max(1.0)).collect();
+
+    // Work in relative changes: x = start + scale * y
+    let lower: Vec<f64> = (0..start.len())
+        .map(|i| (problem.bounds[i].0 - start[i]) / scale[i])
+        .collect();
+    let upper: Vec<f64> = (0..start.len())
+        .map(|i| (problem.bounds[i].1 - start[i]) / scale[i])
+        .collect();
+    if lower.iter().zip(&upper).any(|(lo, hi)| lo > hi) {
+        return None;
+    }
+
+    let rows = normalized_rows(problem, &scale)?;
+    let primal = |multipliers: &[f64]| -> Vec<f64> {
+        (0..start.len())
+            .map(|i| {
+                let pull: f64 = rows.iter().zip(multipliers).map(|(r, m)| r.c[i] * m).sum();
+                (-pull).clamp(lower[i], upper[i])
+            })
+            .collect()
+    };
+    let residuals = |y: &[f64]| -> Vec<f64> { rows.iter().map(|r| dot(&r.c, y) - r.b).collect() };
+
+    // Normalized rows keep the dual gradient's Lipschitz constant below the row count
+    let step = 1.0 / rows.len().max(1) as f64;
+    let mut multipliers = vec![0.0; rows.len()];
+    let mut previous = multipliers.clone();
+    let mut momentum = 1.0_f64;
+
+    for _ in 0..MAX_ITERATIONS {
+        let y = primal(&multipliers);
+        let residual = residuals(&y);
+        if converged(&rows, &multipliers, &residual) {
+            break;
+        }
+
+        let next_momentum = (1.0 + (1.0 + 4.0 * momentum * momentum).sqrt()) / 2.0;
+        let beta = (momentum - 1.0) / next_momentum;
+        let extrapolated: Vec<f64> = multipliers
+            .iter()
+            .zip(&previous)
+            .map(|(m, p)| m + beta * (m - p))
+            .collect();
+
+        let gradient = residuals(&primal(&extrapolated));
+        let updated: Vec<f64> = rows
+            .iter()
+            .zip(extrapolated.iter().zip(&gradient))
+            .map(|(row, (m, g))| {
+                let value = m + step * g;
+                if row.equality { value } else { value.max(0.0) }
+            })
+            .collect();
+
+        // Restart the momentum when it starts working against the ascent direction
+        let progress: f64 = gradient
+            .iter()
+            .zip(updated.iter().zip(&multipliers))
+            .map(|(g, (u, m))| g * (u - m))
+            .sum();
+        momentum = if progress < 0.0 { 1.0 } else { next_momentum };
+
+        previous = std::mem::replace(&mut multipliers, updated);
+    }
+
+    let y = primal(&multipliers);
+    let feasible = rows.iter().zip(residuals(&y)).all(|(row, residual)| {
+        if row.equality {
+            residual.abs() <= FEASIBILITY_TOLERANCE
+        } else {
+            residual <= FEASIBILITY_TOLERANCE
+        }
+    });
+    feasible.then(|| {
+        (0..start.len())
+            .map(|i| (start[i] + scale[i] * y[i]).clamp(problem.bounds[i].0, problem.bounds[i].1))
+            .collect()
+    })
+}
+
+/// Rewrite the constraints in terms of relative changes, one unit-length row per side
+fn normalized_rows(problem: &LeastChangeProblem, scale: &[f64]) -> Option<Vec<Row>> {
+    let mut rows = Vec::new();
+    for constraint in &problem.constraints {
+        let c: Vec<f64> = constraint
+            .coefficients
+            .iter()
+            .zip(scale)
+            .map(|(a, s)| a * s)
+            .collect();
+        let offset = dot(&constraint.coefficients, &problem.start);
+        let norm = dot(&c, &c).sqrt();
+
+        if norm == 0.0 {
+            // Nothing can change this value, so it either holds already or never will
+            if offset < constraint.min - TOLERANCE || offset > constraint.max + TOLERANCE {
+                return None;
+            }
+            continue;
+        }
+        let c: Vec<f64> = c.iter().map(|v| v / norm).collect();
+
+        if constraint.min == constraint.max {
+            rows.push(Row {
+                c,
+                b: (constraint.min - offset) / norm,
+                equality: true,
+            });
+            continue;
+        }
+        if constraint.max.is_finite() {
+            rows.push(Row {
+                c: c.clone(),
+                b: (constraint.max - offset) / norm,
+                equality: false,
+            });
+        }
+        if constraint.min.is_finite() {
+            rows.push(Row {
+                c: c.iter().map(|v| -v).collect(),
+                b: -(constraint.min - offset) / norm,
+                equality: false,
+            });
+        }
+    }
+    Some(rows)
+}
+
+/// Constraints hold and inactive inequalities carry no multiplier
+fn converged(rows: &[Row], multipliers: &[f64], residual: &[f64]) -> bool {
+    rows.iter()
+        .zip(multipliers.iter().zip(residual))
+        .all(|(row, (m, r))| {
+            if row.equality {
+                r.abs() <= TOLERANCE
+            } else {
+                *r <= TOLERANCE && (m * r).abs() <= TOLERANCE
+            }
+        })
+}
+
+fn dot(a: &[f64], b: &[f64]) -> f64 {
+    a.iter().zip(b).map(|(x, y)| x * y).sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(actual: &[f64], expected: &[f64]) {
+        for (a, e) in actual.iter().zip(expected) {
+            assert!((a - e).abs() < 1e-3, "{:?} != {:?}", actual, expected);
+        }
+    }
+
+    #[test]
+    fn test_unconstrained_keeps_start() {
+        let problem = LeastChangeProblem {
+            start: vec![100.0, 50.0],
+            bounds: vec![(0.0, f64::INFINITY); 2],
+            constraints: vec![],
+        };
+        assert_close(&solve(&problem).unwrap(), &[100.0, 50.0]);
+    }
+
+    #[test]
+    fn test_equality_moves_larger_values_more() {
+        // Minimizing squared relative changes splits the +150 in proportion to the
+        // squared starting values (4:1), not evenly in relative terms
+        let problem = LeastChangeProblem {
+            start: vec![100.0, 50.0],
+            bounds: vec![(0.0, f64::INFINITY); 2],
+            constraints: vec![LinearConstraint {
+                coefficients: vec![1.0, 1.0],
+                min: 300.0,
+                max: 300.0,
+            }],
+        };
+        assert_close(&solve(&problem).unwrap(), &[220.0, 80.0]);
+    }
+
+    #[test]
+    fn test_bounds_and_inequalities() {
+        let problem = LeastChangeProblem {
+            start: vec![100.0, 100.0],
+            bounds: vec![(100.0, 100.0), (0.0, f64::INFINITY)],
+            constraints: vec![LinearConstraint {
+                coefficients: vec![1.0, 1.0],
+                min: f64::NEG_INFINITY,
+                max: 150.0,
+            }],
+        };
+        assert_close(&solve(&problem).unwrap(), &[100.0, 50.0]);
+    }
+
+    #[test]
+    fn test_infeasible_problem() {
+        let problem = LeastChangeProblem {
+            start: vec![100.0],
+            bounds: vec![(0.0, 120.0)],
+            constraints: vec![LinearConstraint {
+                coefficients: vec![1.0],
+                min: 200.0,
+                max: f64::INFINITY,
+            }],
+        };
+        assert!(solve(&problem).is_none());
+    }
+}
@@ -1 +1,4 @@
+//! Math and string helpers used by the commands, not part of the library API
+
+pub mod least_change;
 pub mod suggestions;
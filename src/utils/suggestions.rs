@@ -1,3 +1,5 @@
+//! "Did you mean" suggestions for mistyped names
+
 /// Find the best suggestion for a given string from a list of candidates
 pub fn find_best_suggestion(target: &str, candidates: &[String]) -> Option<String> {
     if candidates.is_empty() {
@@ -103,3 +103,13 @@ fn test_early_return_error_propagation_consistency() {
     let normalized_stderr = normalize_temp_paths(&stderr, temp_dir.path());
     assert_snapshot!("early_return_recipe_error_format", normalized_stderr);
 }
+
+#[test]
+fn test_tui_requires_terminal() {
+    // The TUI refuses to start when output is piped instead of drawing garbage
+    let assert = cargo_bin_cmd!("nutriterm").arg("tui").assert().failure();
+
+    let output = assert.get_output();
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("'nutriterm tui' needs an interactive terminal"));
+}
@@ -77,6 +77,100 @@ pub fn write_files(catalog_dir: &Path, ingredients_content: &str, recipes_conten
     fs::write(catalog_dir.join("recipes.jsonc"), recipes_content).unwrap();
 }
 
+/// Ingredients of the example catalog shared by command tests
+#[allow(dead_code)]
+pub const EXAMPLE_INGREDIENTS: &str = r#"{
+  "ingredients": [
+    {
+      "id": "chicken_breast",
+      "name": "Chicken Breast (skinless)",
+      "carbs_per_100g": 0,
+      "protein_per_100g": 31,
+      "fat_per_100g": 3.6,
+      "fiber_per_100g": 0
+    },
+    {
+      "id": "brown_rice",
+      "name": "Brown Rice (cooked)",
+      "carbs_per_100g": 23,
+      "protein_per_100g": 2.6,
+      "fat_per_100g": 0.9,
+      "fiber_per_100g": 1.8
+    },
+    {
+      "id": "oats",
+      "name": "Rolled Oats",
+      "carbs_per_100g": 56,
+      "protein_per_100g": 13,
+      "fat_per_100g": 7,
+      "fiber_per_100g": 10
+    },
+    {
+      "id": "olive_oil",
+      "name": "Extra Virgin Olive Oil",
+      "carbs_per_100g": 0,
+      "protein_per_100g": 0,
+      "fat_per_100g": 100,
+      "fiber_per_100g": 0
+    }
+  ]
+}"#;
+
+/// Recipes of the example catalog: "chicken" matches two of them, and the tags differ in case
+#[allow(dead_code)]
+pub const EXAMPLE_RECIPES: &str = r#"{
+  "recipes": [
+    {
+      "name": "Porridge",
+      "tags": ["breakfast"],
+      "ingredients": [{"id": "oats", "grams": 80}]
+    },
+    {
+      "name": "Chicken Rice Bowl",
+      "tags": ["lunch", "high-protein"],
+      "ingredients": [
+        {"id": "chicken_breast", "grams": 150},
+        {"id": "brown_rice", "grams": 100},
+        {"id": "olive_oil", "grams": 10}
+      ]
+    },
+    {
+      "name": "Plain Chicken",
+      "tags": ["High-Protein"],
+      "ingredients": [
+        {"id": "chicken_breast", "grams": 200},
+        {"id": "olive_oil", "grams": 5}
+      ]
+    }
+  ]
+}"#;
+
+/// Create a temp directory holding the example catalog
+#[allow(dead_code)]
+pub fn example_catalog_dir() -> TempDir {
+    let temp = temp_dir();
+    write_files(temp.path(), EXAMPLE_INGREDIENTS, EXAMPLE_RECIPES);
+    temp
+}
+
+/// Read a file from a test catalog directory
+#[allow(dead_code)]
+pub fn read(temp: &TempDir, filename: &str) -> String {
+    fs::read_to_string(temp.path().join(filename)).unwrap()
+}
+
+/// Run nutriterm, failing the test with its stderr unless it succeeds; returns stdout without colors
+#[allow(dead_code)]
+pub fn run_ok(args: &[&str], working_dir: &Path) -> String {
+    let output = run_cmd(args, working_dir);
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    strip_ansi_codes(&String::from_utf8_lossy(&output.stdout))
+}
+
 /// Helper function to manually create catalog files (without running init command)
 #[allow(dead_code)] // Used across multiple test modules, but Rust can't track cross-module test usage
 pub fn create_catalog_files(catalog_dir: &std::path::Path) {
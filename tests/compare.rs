@@ -0,0 +1,37 @@
+use insta::assert_snapshot;
+
+mod common;
+use common::{example_catalog_dir, run_cmd, run_ok, strip_ansi_codes};
+
+#[test]
+fn test_compare_two_recipes() {
+    let temp = example_catalog_dir();
+    let output = run_cmd(&["compare", "bowl", "plain"], temp.path());
+    assert!(output.status.success());
+    assert_snapshot!(format!(
+        "$ nutriterm compare bowl plain\n{}",
+        strip_ansi_codes(&String::from_utf8_lossy(&output.stdout)).trim_end()
+    ));
+}
+
+#[test]
+fn test_compare_requires_two_recipes() {
+    let temp = example_catalog_dir();
+    let output = run_cmd(&["compare", "bowl"], temp.path());
+    assert!(!output.status.success());
+}
+
+#[test]
+fn test_compare_ambiguous_recipe() {
+    let temp = example_catalog_dir();
+    let output = run_cmd(&["compare", "chicken", "plain"], temp.path());
+    assert!(!output.status.success());
+    assert_snapshot!(String::from_utf8_lossy(&output.stderr));
+}
+
+#[test]
+fn test_compare_recipe_with_itself_marks_nothing_unique() {
+    let temp = example_catalog_dir();
+    let output = run_ok(&["compare", "bowl", "bowl"], temp.path());
+    assert!(!output.contains("Not used in the other compared recipes"));
+}
@@ -0,0 +1,68 @@
+use assert_cmd::cargo::cargo_bin_cmd;
+
+mod common;
+use common::example_catalog_dir;
+
+/// What the fish script asks for when tab is pressed after `words`
+fn fish_completions(words: &[&str]) -> String {
+    let temp = example_catalog_dir();
+    let output = cargo_bin_cmd!("nutriterm")
+        .env("COMPLETE", "fish")
+        .arg("--")
+        .arg("nutriterm")
+        .args(words)
+        .current_dir(temp.path())
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    String::from_utf8_lossy(&output.stdout).to_string()
+}
+
+#[test]
+fn test_completion_scripts_for_each_shell() {
+    for (shell, expected) in [
+        ("bash", "-o filenames -F _clap_complete_nutriterm nutriterm"),
+        ("zsh", "compdef _clap_dynamic_completer_nutriterm nutriterm"),
+        (
+            "fish",
+            "complete --keep-order --exclusive --command nutriterm",
+        ),
+    ] {
+        let output = cargo_bin_cmd!("nutriterm")
+            .args(["completions", shell])
+            .output()
+            .unwrap();
+        assert!(output.status.success());
+        let script = String::from_utf8_lossy(&output.stdout);
+        assert!(script.contains(expected), "{} script:\n{}", shell, script);
+        assert!(
+            script.contains(&format!("COMPLETE={}", shell))
+                || script.contains(&format!("COMPLETE=\"{}\"", shell))
+        );
+    }
+}
+
+#[test]
+fn test_recipe_names_complete_from_catalog() {
+    assert_eq!(
+        fish_completions(&["recipe", "p"]),
+        "Porridge\nPlain Chicken\n"
+    );
+    // Falls back to names containing the text when none start with it
+    assert_eq!(
+        fish_completions(&["compare", "bowl"]),
+        "Chicken Rice Bowl\n"
+    );
+}
+
+#[test]
+fn test_ingredient_ids_complete_from_catalog() {
+    assert_eq!(
+        fish_completions(&["ingredient", "br"]),
+        "brown_rice\tBrown Rice (cooked)\n"
+    );
+    assert_eq!(
+        fish_completions(&["recipe", "add", "Bowl", "--ingredient", "chi"]),
+        "chicken_breast=\tChicken Breast (skinless)\n"
+    );
+}
@@ -0,0 +1,141 @@
+use insta::assert_snapshot;
+use std::fs;
+
+mod common;
+use common::{example_catalog_dir, read, run_cmd, run_ok};
+
+#[test]
+fn test_log_appends_snapshot_entries() {
+    let temp = example_catalog_dir();
+    let mut transcript = String::new();
+    for args in [
+        &[
+            "log",
+            "porridge",
+            "1.5x",
+            "--date",
+            "2026-03-02",
+            "--meal",
+            "Breakfast",
+        ][..],
+        &["log", "oats", "30", "--date", "2026-03-02"],
+        &[
+            "log",
+            "plain",
+            "150g",
+            "--date",
+            "2026-03-02",
+            "--meal",
+            "Lunch",
+        ],
+    ] {
+        transcript.push_str(&format!("$ nutriterm {}\n", args.join(" ")));
+        transcript.push_str(&run_ok(args, temp.path()));
+    }
+    assert_snapshot!("log_output", transcript);
+
+    let diary = read(&temp, "diary.jsonc");
+    assert_snapshot!("diary_file", diary);
+}
+
+#[test]
+fn test_day_uses_values_from_logging_time() {
+    let temp = example_catalog_dir();
+    run_ok(
+        &["log", "porridge", "1", "--date", "2026-03-02"],
+        temp.path(),
+    );
+    run_ok(
+        &[
+            "log",
+            "chicken_breast",
+            "100g",
+            "--date",
+            "2026-03-02",
+            "--meal",
+            "Dinner",
+        ],
+        temp.path(),
+    );
+    run_ok(
+        &["log", "porridge", "1", "--date", "2026-03-03"],
+        temp.path(),
+    );
+
+    // Editing an ingredient later does not change what was logged
+    let ingredients = read(&temp, "ingredients.jsonc");
+    fs::write(
+        temp.path().join("ingredients.jsonc"),
+        ingredients.replace("\"protein_per_100g\": 13", "\"protein_per_100g\": 99"),
+    )
+    .unwrap();
+
+    assert_snapshot!(
+        "day",
+        format!(
+            "$ nutriterm day 2026-03-02\n{}",
+            run_ok(&["day", "2026-03-02"], temp.path()).trim_end()
+        )
+    );
+}
+
+#[test]
+fn test_day_defaults_to_today() {
+    let temp = example_catalog_dir();
+    run_ok(&["log", "porridge", "1"], temp.path());
+    let output = run_ok(&["day"], temp.path());
+    assert!(output.contains("Porridge"));
+}
+
+#[test]
+fn test_day_without_entries() {
+    let temp = example_catalog_dir();
+    assert_snapshot!(
+        "empty_day",
+        run_ok(&["day", "2026-03-02"], temp.path()).trim_end()
+    );
+}
+
+#[test]
+fn test_report_averages_logged_days() {
+    let temp = example_catalog_dir();
+    run_ok(
+        &["log", "porridge", "1", "--date", "2026-03-01"],
+        temp.path(),
+    );
+    run_ok(
+        &["log", "porridge", "2", "--date", "2026-03-03"],
+        temp.path(),
+    );
+    run_ok(&["log", "plain", "1", "--date", "2026-03-03"], temp.path());
+    run_ok(&["log", "plain", "1", "--date", "2026-03-09"], temp.path());
+
+    assert_snapshot!(
+        "report",
+        format!(
+            "$ nutriterm report --from 2026-03-01 --to 2026-03-07\n{}",
+            run_ok(
+                &["report", "--from", "2026-03-01", "--to", "2026-03-07"],
+                temp.path()
+            )
+            .trim_end()
+        )
+    );
+}
+
+#[test]
+fn test_log_rejects_ambiguous_food_and_ingredient_portions() {
+    let temp = example_catalog_dir();
+
+    let output = run_cmd(&["log", "chicken", "1"], temp.path());
+    assert!(!output.status.success());
+    assert_snapshot!("ambiguous_food", String::from_utf8_lossy(&output.stderr));
+
+    let output = run_cmd(&["log", "oats", "2x"], temp.path());
+    assert!(!output.status.success());
+    assert_snapshot!(
+        "ingredient_portions",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(!temp.path().join("diary.jsonc").exists());
+}
@@ -0,0 +1,256 @@
+use insta::assert_snapshot;
+
+mod common;
+use common::{normalize_temp_paths, read, run_cmd, temp_dir, write_files};
+
+const INGREDIENTS: &str = r#"{
+  // Values from the package labels
+  "ingredients": [
+    {
+      "id": "chicken_breast",
+      "name": "Chicken Breast (skinless)",
+      "carbs_per_100g": 0,
+      "protein_per_100g": 31,
+      "fat_per_100g": 3.6,
+      "fiber_per_100g": 0
+    },
+    {
+      "id": "brown_rice", // cooked weight
+      "name": "Brown Rice (cooked)",
+      "carbs_per_100g": 23,
+      "protein_per_100g": 2.6,
+      "fat_per_100g": 0.9,
+      "fiber_per_100g": 1.8
+    }
+  ]
+}"#;
+
+const RECIPES: &str = r#"{
+  "recipes": [
+    {
+      "name": "Chicken Rice Bowl",
+      "ingredients": [
+        {"id": "chicken_breast", "grams": 150}, // one fillet
+        {"id": "brown_rice", "grams": 100}
+      ]
+    },
+    {
+      "name": "Chicken Rice Soup",
+      "ingredients": [{"id": "chicken_breast", "grams": 80}]
+    }
+    // Keep the soup last
+  ]
+}"#;
+
+fn create_edit_catalog_dir() -> tempfile::TempDir {
+    let temp = temp_dir();
+    write_files(temp.path(), INGREDIENTS, RECIPES);
+    temp
+}
+
+#[test]
+fn test_edits_preserve_comments_and_order() {
+    let temp = create_edit_catalog_dir();
+
+    let steps: [&[&str]; 4] = [
+        &[
+            "ingredient",
+            "add",
+            "broccoli",
+            "--name",
+            "Broccoli",
+            "--carbs",
+            "7",
+            "--protein",
+            "2.8",
+            "--fat",
+            "0.4",
+            "--fiber",
+            "2.6",
+        ],
+        &[
+            "recipe",
+            "add",
+            "Broccoli Side",
+            "--tag",
+            "side",
+            "--ingredient",
+            "broccoli=200",
+        ],
+        &["recipe", "set", "bowl", "brown_rice", "80.5"],
+        &[
+            "recipe",
+            "remove-ingredient",
+            "Chicken Rice Soup",
+            "chicken_breast",
+        ],
+    ];
+
+    let mut transcript = String::new();
+    for args in steps {
+        let output = run_cmd(args, temp.path());
+        assert!(output.status.success(), "{:?} failed", args);
+        transcript.push_str(&format!(
+            "$ nutriterm {}\n{}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stdout)
+        ));
+    }
+
+    assert_snapshot!("transcript", transcript);
+    assert_snapshot!("ingredients_after", read(&temp, "ingredients.jsonc"));
+    assert_snapshot!("recipes_after", read(&temp, "recipes.jsonc"));
+}
+
+#[test]
+fn test_edit_refused_when_catalog_would_break() {
+    let temp = create_edit_catalog_dir();
+
+    let output = run_cmd(
+        &["recipe", "set", "Chicken Rice Bowl", "chiken_breast", "100"],
+        temp.path(),
+    );
+
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert_snapshot!("unknown_ingredient_refused", stderr);
+    assert_eq!(read(&temp, "recipes.jsonc"), RECIPES);
+}
+
+#[test]
+fn test_add_existing_ingredient_refused() {
+    let temp = create_edit_catalog_dir();
+
+    let output = run_cmd(
+        &[
+            "ingredient",
+            "add",
+            "brown_rice",
+            "--name",
+            "Rice",
+            "--carbs",
+            "1",
+            "--protein",
+            "1",
+            "--fat",
+            "1",
+            "--fiber",
+            "1",
+        ],
+        temp.path(),
+    );
+
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert_snapshot!("duplicate_ingredient_refused", stderr);
+    assert_eq!(read(&temp, "ingredients.jsonc"), INGREDIENTS);
+}
+
+#[test]
+fn test_edit_with_ambiguous_recipe() {
+    let temp = create_edit_catalog_dir();
+
+    let output = run_cmd(
+        &["recipe", "remove-ingredient", "chicken rice", "brown_rice"],
+        temp.path(),
+    );
+
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert_snapshot!(
+        "ambiguous_recipe",
+        normalize_temp_paths(&stderr, temp.path())
+    );
+}
+
+#[test]
+fn test_rename_ingredient_dry_run_shows_diff() {
+    let temp = create_edit_catalog_dir();
+
+    let output = run_cmd(
+        &[
+            "ingredient",
+            "rename",
+            "chicken_breast",
+            "chicken_breast_raw",
+            "--dry-run",
+        ],
+        temp.path(),
+    );
+
+    assert!(output.status.success());
+    assert_snapshot!("rename_dry_run", String::from_utf8_lossy(&output.stdout));
+    assert_eq!(read(&temp, "recipes.jsonc"), RECIPES);
+    assert_eq!(read(&temp, "ingredients.jsonc"), INGREDIENTS);
+}
+
+#[test]
+fn test_rename_ingredient_updates_all_references() {
+    let temp = create_edit_catalog_dir();
+
+    let output = run_cmd(
+        &[
+            "ingredient",
+            "rename",
+            "chicken_breast",
+            "chicken_breast_raw",
+        ],
+        temp.path(),
+    );
+
+    assert!(output.status.success());
+    assert_snapshot!("rename_output", String::from_utf8_lossy(&output.stdout));
+    assert_snapshot!("rename_recipes_after", read(&temp, "recipes.jsonc"));
+
+    let lookup = run_cmd(&["ingredient", "chicken_breast_raw"], temp.path());
+    assert!(String::from_utf8_lossy(&lookup.stdout).contains("Used in 2 recipes"));
+}
+
+#[test]
+fn test_rename_to_existing_id_refused() {
+    let temp = create_edit_catalog_dir();
+
+    let output = run_cmd(
+        &["ingredient", "rename", "chicken_breast", "brown_rice"],
+        temp.path(),
+    );
+
+    assert!(!output.status.success());
+    assert_snapshot!(
+        "rename_existing_refused",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert_eq!(read(&temp, "ingredients.jsonc"), INGREDIENTS);
+}
+
+#[test]
+fn test_rename_ingredient_updates_pantry() {
+    let temp = create_edit_catalog_dir();
+    assert!(
+        run_cmd(&["pantry", "add", "brown_rice", "500"], temp.path())
+            .status
+            .success()
+    );
+
+    let output = run_cmd(
+        &["ingredient", "rename", "brown_rice", "rice_brown_cooked"],
+        temp.path(),
+    );
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("pantry updated"), "{}", stdout);
+    assert!(read(&temp, "pantry.jsonc").contains("\"rice_brown_cooked\""));
+
+    let pantry = run_cmd(&["pantry"], temp.path());
+    assert!(
+        pantry.status.success(),
+        "{}",
+        String::from_utf8_lossy(&pantry.stderr)
+    );
+    assert!(String::from_utf8_lossy(&pantry.stdout).contains("Brown Rice (cooked)"));
+    assert!(
+        run_cmd(&["pantry", "add", "rice_brown_cooked", "100"], temp.path())
+            .status
+            .success()
+    );
+}
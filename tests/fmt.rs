@@ -0,0 +1,87 @@
+use insta::assert_snapshot;
+
+mod common;
+use common::{read, run_cmd, temp_dir, write_files};
+
+const MESSY_RECIPES: &str = r#"{
+  // Weekly rotation
+  "recipes": [
+    { "name": "Zucchini Pasta", "tags": [ "dinner","vegetarian" ], "ingredients": [ {"id": "zucchini", "grams": 250.0}, // two small ones
+      {"id": "olive_oil", "grams": 1e1} ] },
+
+
+    /* quick */ {
+        "name": "Apple Snack",
+        "ingredients": [{"id": "apple", "grams": 150.50},],
+    },
+    // Add more recipes here
+  ],
+}"#;
+
+const MESSY_INGREDIENTS: &str = r#"{"ingredients": [
+  {"id": "zucchini", "name": "Zucchini", "carbs_per_100g": 2.1, "protein_per_100g": 1.2, "fat_per_100g": 0.3, "fiber_per_100g": 1.0},
+  {"id": "olive_oil", "name": "Olive Oil", "carbs_per_100g": 0, "protein_per_100g": 0, "fat_per_100g": 100, "fiber_per_100g": 0},
+  {"id": "apple", "name": "Apple", "carbs_per_100g": 11.4, "protein_per_100g": 0.3, "fat_per_100g": 0.2, "fiber_per_100g": 2.4}
+]}"#;
+
+fn create_messy_catalog_dir() -> tempfile::TempDir {
+    let temp = temp_dir();
+    write_files(temp.path(), MESSY_INGREDIENTS, MESSY_RECIPES);
+    temp
+}
+
+#[test]
+fn test_fmt_rewrites_layout_keeping_comments() {
+    let temp = create_messy_catalog_dir();
+
+    let output = run_cmd(&["fmt"], temp.path());
+
+    assert!(output.status.success());
+    assert_snapshot!("fmt_output", String::from_utf8_lossy(&output.stdout));
+    assert_snapshot!("recipes_formatted", read(&temp, "recipes.jsonc"));
+    assert_snapshot!("ingredients_formatted", read(&temp, "ingredients.jsonc"));
+
+    // Formatting is stable
+    let check = run_cmd(&["fmt", "--check"], temp.path());
+    assert!(check.status.success());
+}
+
+#[test]
+fn test_fmt_sorting_and_number_normalization() {
+    let temp = create_messy_catalog_dir();
+
+    let args = [
+        "fmt",
+        "--sort-recipes",
+        "--sort-ingredients",
+        "--normalize-numbers",
+    ];
+    let output = run_cmd(&args, temp.path());
+
+    assert!(output.status.success());
+    assert_snapshot!("recipes_sorted", read(&temp, "recipes.jsonc"));
+    assert_snapshot!("ingredients_sorted", read(&temp, "ingredients.jsonc"));
+}
+
+#[test]
+fn test_fmt_check_fails_without_writing() {
+    let temp = create_messy_catalog_dir();
+
+    let output = run_cmd(&["fmt", "--check"], temp.path());
+
+    assert!(!output.status.success());
+    assert_snapshot!("check_diff", String::from_utf8_lossy(&output.stdout));
+    assert_snapshot!("check_error", String::from_utf8_lossy(&output.stderr));
+    assert_eq!(read(&temp, "recipes.jsonc"), MESSY_RECIPES);
+    assert_eq!(read(&temp, "ingredients.jsonc"), MESSY_INGREDIENTS);
+}
+
+#[test]
+fn test_initialized_catalog_is_formatted() {
+    let temp = temp_dir();
+    assert!(run_cmd(&["init"], temp.path()).status.success());
+
+    let output = run_cmd(&["fmt", "--check"], temp.path());
+
+    assert!(output.status.success());
+}
@@ -0,0 +1,103 @@
+use insta::assert_snapshot;
+
+mod common;
+use common::{run_cmd, strip_ansi_codes, temp_dir, write_files};
+
+fn create_ingredient_catalog_dir() -> tempfile::TempDir {
+    let temp = temp_dir();
+    write_files(
+        temp.path(),
+        r#"{
+  "ingredients": [
+    {
+      "id": "chicken_breast",
+      "name": "Chicken Breast (skinless)",
+      "carbs_per_100g": 0,
+      "protein_per_100g": 31,
+      "fat_per_100g": 3.6,
+      "fiber_per_100g": 0
+    },
+    {
+      "id": "chicken_thigh",
+      "name": "Chicken Thigh",
+      "carbs_per_100g": 0,
+      "protein_per_100g": 24,
+      "fat_per_100g": 9,
+      "fiber_per_100g": 0
+    },
+    {
+      "id": "brown_rice",
+      "name": "Brown Rice (cooked)",
+      "carbs_per_100g": 23,
+      "protein_per_100g": 2.6,
+      "fat_per_100g": 0.9,
+      "fiber_per_100g": 1.8
+    },
+    {
+      "id": "water",
+      "name": "Water",
+      "carbs_per_100g": 0,
+      "protein_per_100g": 0,
+      "fat_per_100g": 0,
+      "fiber_per_100g": 0
+    }
+  ]
+}"#,
+        r#"{
+  "recipes": [
+    {
+      "name": "Chicken Rice Bowl",
+      "ingredients": [
+        {"id": "chicken_breast", "grams": 150},
+        {"id": "brown_rice", "grams": 100}
+      ]
+    },
+    {
+      "name": "Rice Pudding",
+      "ingredients": [
+        {"id": "brown_rice", "grams": 120},
+        {"id": "brown_rice", "grams": 30}
+      ]
+    }
+  ]
+}"#,
+    );
+    temp
+}
+
+fn ingredient_snapshot(query: &str) -> String {
+    let temp = create_ingredient_catalog_dir();
+    let output = run_cmd(&["ingredient", query], temp.path());
+    assert!(output.status.success());
+    format!(
+        "$ nutriterm ingredient {}\n{}",
+        query,
+        strip_ansi_codes(&String::from_utf8_lossy(&output.stdout)).trim_end()
+    )
+}
+
+#[test]
+fn test_ingredient_by_id_shows_usage() {
+    // Grams are summed when a recipe lists the same ingredient twice
+    assert_snapshot!("by_id", ingredient_snapshot("brown_rice"));
+}
+
+#[test]
+fn test_ingredient_search_by_name_terms() {
+    assert_snapshot!("search_by_name", ingredient_snapshot("breast chicken"));
+}
+
+#[test]
+fn test_ingredient_search_multiple_matches() {
+    assert_snapshot!("multiple_matches", ingredient_snapshot("chicken"));
+}
+
+#[test]
+fn test_ingredient_no_match_suggests_id() {
+    assert_snapshot!("no_match_suggestion", ingredient_snapshot("brwn_rice"));
+}
+
+#[test]
+fn test_ingredient_unused_without_calories() {
+    assert_snapshot!("unused", ingredient_snapshot("Water"));
+}
@@ -30,6 +30,12 @@ fn test_init_in_empty_directory() {
     assert!(catalog_dir.join("ingredients.schema.json").exists());
     assert!(catalog_dir.join("recipes.jsonc").exists());
     assert!(catalog_dir.join("ingredients.jsonc").exists());
+    assert!(catalog_dir.join("plans.schema.json").exists());
+    assert!(catalog_dir.join("plans.jsonc").exists());
+    assert!(catalog_dir.join("diary.schema.json").exists());
+    assert!(catalog_dir.join("targets.schema.json").exists());
+    assert!(catalog_dir.join("pantry.schema.json").exists());
+    assert!(catalog_dir.join("targets.jsonc").exists());
 
     // Snapshot the content of created files to ensure they're properly formatted
     let ingredients_content =
@@ -45,6 +51,24 @@ fn test_init_in_empty_directory() {
 
     let recipes_schema = std::fs::read_to_string(catalog_dir.join("recipes.schema.json")).unwrap();
     assert_snapshot!("recipes_schema", recipes_schema);
+
+    let plans_content = std::fs::read_to_string(catalog_dir.join("plans.jsonc")).unwrap();
+    assert_snapshot!("plans_content", plans_content);
+
+    let plans_schema = std::fs::read_to_string(catalog_dir.join("plans.schema.json")).unwrap();
+    assert_snapshot!("plans_schema", plans_schema);
+
+    let diary_schema = std::fs::read_to_string(catalog_dir.join("diary.schema.json")).unwrap();
+    assert_snapshot!("diary_schema", diary_schema);
+
+    let targets_content = std::fs::read_to_string(catalog_dir.join("targets.jsonc")).unwrap();
+    assert_snapshot!("targets_content", targets_content);
+
+    let targets_schema = std::fs::read_to_string(catalog_dir.join("targets.schema.json")).unwrap();
+    assert_snapshot!("targets_schema", targets_schema);
+
+    let pantry_schema = std::fs::read_to_string(catalog_dir.join("pantry.schema.json")).unwrap();
+    assert_snapshot!("pantry_schema", pantry_schema);
 }
 
 #[test]
@@ -158,3 +158,201 @@ fn test_kitchen_ref_success() {
     let stdout = String::from_utf8_lossy(&output.stdout);
     assert_snapshot!("output", stdout);
 }
+
+#[test]
+fn test_kitchen_ref_escapes_names_and_links_contents() {
+    let temp = temp_dir();
+    let catalog_dir = catalog_dir(&temp, "catalog_dir");
+    create_kitchen_ref_catalog_dir(&catalog_dir);
+    let recipes = fs::read_to_string(catalog_dir.join("recipes.jsonc"))
+        .unwrap()
+        .replace("Chicken Rice Bowl", "Mac & Cheese <deluxe>")
+        .replace("Greek Salad", "Mac Cheese Deluxe");
+    fs::write(catalog_dir.join("recipes.jsonc"), recipes).unwrap();
+
+    let mut cmd = cargo_bin_cmd!("nutriterm");
+    let assert = cmd
+        .current_dir(&catalog_dir)
+        .arg("kitchen-ref")
+        .assert()
+        .success();
+
+    let stdout = String::from_utf8_lossy(&assert.get_output().stdout).to_string();
+    assert!(stdout.contains("<h2>Mac &amp; Cheese &lt;deluxe&gt;</h2>"));
+    assert!(!stdout.contains("<deluxe>"));
+    // Both names come down to the same words, so the second anchor gets a number
+    assert!(
+        stdout.contains(
+            "<li><a href=\"#mac-cheese-deluxe\">Mac &amp; Cheese &lt;deluxe&gt;</a></li>"
+        )
+    );
+    assert!(stdout.contains("<li><a href=\"#mac-cheese-deluxe-2\">Mac Cheese Deluxe</a></li>"));
+    assert!(stdout.contains("<section class=\"recipe\" id=\"mac-cheese-deluxe-2\">"));
+    assert!(stdout.contains("@media print"));
+}
+
+#[test]
+fn test_kitchen_ref_custom_template() {
+    let temp = temp_dir();
+    let catalog_dir = catalog_dir(&temp, "catalog_dir");
+    create_kitchen_ref_catalog_dir(&catalog_dir);
+    fs::write(
+        catalog_dir.join("cards.html"),
+        "<html><title>{{ title }}</title><body class=\"cards\">\n{{recipes}}</body></html>\n",
+    )
+    .unwrap();
+
+    let mut cmd = cargo_bin_cmd!("nutriterm");
+    let assert = cmd
+        .current_dir(&catalog_dir)
+        .args(["kitchen-ref", "--template", "cards.html"])
+        .assert()
+        .success();
+
+    let stdout = String::from_utf8_lossy(&assert.get_output().stdout).to_string();
+    assert!(stdout.starts_with("<html><title>Kitchen Reference</title><body class=\"cards\">\n"));
+    assert!(stdout.contains("<section class=\"recipe\" id=\"greek-salad\">"));
+    assert!(!stdout.contains("<nav>"));
+
+    fs::write(catalog_dir.join("typo.html"), "<body>{{recipe}}</body>").unwrap();
+    let mut cmd = cargo_bin_cmd!("nutriterm");
+    let assert = cmd
+        .current_dir(&catalog_dir)
+        .args(["kitchen-ref", "--template", "typo.html"])
+        .assert()
+        .failure();
+    assert_snapshot!(
+        "template_unknown_placeholder",
+        String::from_utf8_lossy(&assert.get_output().stderr)
+    );
+}
+
+/// The test catalog with a lunch tag on the bowl and a third recipe tagged dinner
+fn create_tagged_catalog_dir(catalog_dir: &std::path::Path) {
+    create_kitchen_ref_catalog_dir(catalog_dir);
+    let recipes = fs::read_to_string(catalog_dir.join("recipes.jsonc"))
+        .unwrap()
+        .replace(
+            "\"name\": \"Chicken Rice Bowl\",",
+            "\"name\": \"Chicken Rice Bowl\",\n      \"tags\": [\"lunch\"],",
+        )
+        .replace(
+            "  ]\n}",
+            r#"  ,
+    {
+      "name": "Apple Chicken Salad",
+      "tags": ["dinner"],
+      "ingredients": [
+        {
+          "id": "chicken_breast",
+          "grams": 120
+        },
+        {
+          "id": "mixed_greens",
+          "grams": 80
+        }
+      ]
+    }
+  ]
+}"#,
+        );
+    fs::write(catalog_dir.join("recipes.jsonc"), recipes).unwrap();
+}
+
+/// Recipe headings of a kitchen reference, in order
+fn headings(html: &str) -> Vec<&str> {
+    html.lines()
+        .filter_map(|line| line.strip_prefix("<h2>")?.strip_suffix("</h2>"))
+        .filter(|heading| *heading != "Contents")
+        .collect()
+}
+
+#[test]
+fn test_kitchen_ref_selection_and_order() {
+    let temp = temp_dir();
+    let catalog_dir = catalog_dir(&temp, "catalog_dir");
+    create_tagged_catalog_dir(&catalog_dir);
+
+    let run = |args: &[&str]| {
+        let mut cmd = cargo_bin_cmd!("nutriterm");
+        let assert = cmd
+            .current_dir(&catalog_dir)
+            .arg("kitchen-ref")
+            .args(args)
+            .assert()
+            .success();
+        String::from_utf8_lossy(&assert.get_output().stdout).to_string()
+    };
+
+    assert_eq!(
+        headings(&run(&[])),
+        ["Chicken Rice Bowl", "Greek Salad", "Apple Chicken Salad"]
+    );
+    assert_eq!(
+        headings(&run(&["--sort", "name"])),
+        ["Apple Chicken Salad", "Chicken Rice Bowl", "Greek Salad"]
+    );
+    assert_eq!(
+        headings(&run(&["--sort", "tag"])),
+        ["Apple Chicken Salad", "Chicken Rice Bowl", "Greek Salad"]
+    );
+    // Search terms may pick several recipes; file order is kept unless asked otherwise
+    assert_eq!(
+        headings(&run(&["salad", "Chicken Rice Bowl"])),
+        ["Chicken Rice Bowl", "Greek Salad", "Apple Chicken Salad"]
+    );
+    assert_eq!(
+        headings(&run(&["chicken", "--tag", "DINNER"])),
+        ["Apple Chicken Salad"]
+    );
+
+    let scaled = run(&["bowl", "--scale", "3"]);
+    assert!(scaled.contains("<h1>Kitchen Reference (×3.00)</h1>"));
+    assert!(
+        scaled.contains("<li><span class=\"weight\">450.0 g</span> Chicken Breast (skinless)</li>")
+    );
+}
+
+#[test]
+fn test_kitchen_ref_selection_errors() {
+    let temp = temp_dir();
+    let catalog_dir = catalog_dir(&temp, "catalog_dir");
+    create_tagged_catalog_dir(&catalog_dir);
+
+    let stderr = |args: &[&str]| {
+        let mut cmd = cargo_bin_cmd!("nutriterm");
+        let assert = cmd
+            .current_dir(&catalog_dir)
+            .arg("kitchen-ref")
+            .args(args)
+            .assert()
+            .failure();
+        String::from_utf8_lossy(&assert.get_output().stderr).to_string()
+    };
+
+    assert_eq!(stderr(&["pizza"]), "Error: No recipe matches 'pizza'\n");
+    assert_eq!(
+        stderr(&["greek", "--tag", "lunch"]),
+        "Error: None of the chosen recipes are tagged lunch\n"
+    );
+    assert!(stderr(&["--scale", "0"]).contains("expected a positive number, got '0'"));
+}
+
+#[test]
+fn test_kitchen_ref_empty_catalog() {
+    let temp = temp_dir();
+    let catalog_dir = catalog_dir(&temp, "catalog_dir");
+    create_kitchen_ref_catalog_dir(&catalog_dir);
+    fs::write(catalog_dir.join("recipes.jsonc"), r#"{"recipes": []}"#).unwrap();
+
+    let mut cmd = cargo_bin_cmd!("nutriterm");
+    let assert = cmd
+        .current_dir(&catalog_dir)
+        .arg("kitchen-ref")
+        .assert()
+        .success();
+
+    let stdout = String::from_utf8_lossy(&assert.get_output().stdout).to_string();
+    assert!(stdout.contains("<h1>Kitchen Reference</h1>"));
+    assert!(headings(&stdout).is_empty());
+}
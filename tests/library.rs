@@ -0,0 +1,52 @@
+use nutriterm::{AppError, catalog};
+
+mod common;
+use common::{EXAMPLE_INGREDIENTS, EXAMPLE_RECIPES, example_catalog_dir, temp_dir, write_files};
+
+#[test]
+fn test_library_loads_and_queries_catalog() {
+    let temp = example_catalog_dir();
+    let catalog = catalog::load_from(temp.path()).unwrap();
+
+    let bowl = catalog.recipe("bowl").unwrap();
+    assert_eq!(bowl.name, "Chicken Rice Bowl");
+    let chicken = bowl.ingredients[0].nutrition();
+    assert_eq!(chicken.grams, 150.0);
+    assert!((chicken.protein - 46.5).abs() < 1e-9);
+    let totals = bowl.totals();
+    assert_eq!(totals.grams, 260.0);
+    let calories: f64 = bowl.ingredients.iter().map(|i| i.calories()).sum();
+    assert!((totals.calories - calories).abs() < 1e-9);
+
+    let rice = catalog.ingredient("brown rice").unwrap();
+    assert_eq!(rice.id, "brown_rice");
+
+    match catalog.recipe("chicken") {
+        Err(AppError::RecipeNotFound { candidates, .. }) => {
+            assert_eq!(candidates, ["Chicken Rice Bowl", "Plain Chicken"]);
+        }
+        other => panic!("expected an ambiguous search, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_library_reports_load_errors() {
+    let temp = temp_dir();
+    write_files(
+        temp.path(),
+        EXAMPLE_INGREDIENTS,
+        &EXAMPLE_RECIPES.replace("\"brown_rice\"", "\"brown_ryce\""),
+    );
+
+    match catalog::load_from(temp.path()) {
+        Err(AppError::UnknownIngredient {
+            ingredient,
+            suggestion,
+            ..
+        }) => {
+            assert_eq!(ingredient, "brown_ryce");
+            assert_eq!(suggestion.as_deref(), Some("brown_rice"));
+        }
+        other => panic!("expected an unknown ingredient, got {:?}", other),
+    }
+}
@@ -0,0 +1,75 @@
+use insta::assert_snapshot;
+
+mod common;
+use common::{example_catalog_dir, run_cmd, strip_ansi_codes};
+
+fn list_snapshot(args: &[&str]) -> String {
+    let temp = example_catalog_dir();
+    let output = run_cmd(args, temp.path());
+    assert!(output.status.success());
+    format!(
+        "$ nutriterm {}\n{}",
+        args.join(" "),
+        strip_ansi_codes(&String::from_utf8_lossy(&output.stdout)).trim_end()
+    )
+}
+
+#[test]
+fn test_list_all_recipes_sorted_by_name() {
+    assert_snapshot!("table_by_name", list_snapshot(&["list"]));
+}
+
+#[test]
+fn test_list_sorted_by_kcal_descending() {
+    assert_snapshot!(
+        "table_by_kcal_reversed",
+        list_snapshot(&["list", "--sort", "kcal", "--reverse"])
+    );
+}
+
+#[test]
+fn test_list_filters_combine() {
+    // Tags match case-insensitively; all filters must hold
+    assert_snapshot!(
+        "plain_filtered",
+        list_snapshot(&[
+            "list",
+            "--tag",
+            "high-protein",
+            "--contains",
+            "chicken_breast",
+            "--max-carbs",
+            "20",
+            "--format",
+            "plain",
+        ])
+    );
+}
+
+#[test]
+fn test_list_no_matches() {
+    assert_snapshot!(
+        "no_matches",
+        list_snapshot(&["list", "--min-protein", "500"])
+    );
+}
+
+#[test]
+fn test_list_json_output() {
+    let temp = example_catalog_dir();
+    let output = run_cmd(
+        &["list", "--sort", "protein", "--format", "json"],
+        temp.path(),
+    );
+    assert!(output.status.success());
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let names: Vec<&str> = json
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|entry| entry["name"].as_str().unwrap())
+        .collect();
+    assert_eq!(names, ["Porridge", "Chicken Rice Bowl", "Plain Chicken"]);
+    assert_snapshot!("json", String::from_utf8_lossy(&output.stdout));
+}
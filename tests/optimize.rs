@@ -0,0 +1,117 @@
+use insta::assert_snapshot;
+
+mod common;
+use common::{run_cmd, strip_ansi_codes, temp_dir, write_files};
+
+fn create_optimize_catalog_dir() -> tempfile::TempDir {
+    let temp = temp_dir();
+    write_files(
+        temp.path(),
+        r#"{
+  "ingredients": [
+    {
+      "id": "chicken_breast",
+      "name": "Chicken Breast (skinless)",
+      "carbs_per_100g": 0,
+      "protein_per_100g": 31,
+      "fat_per_100g": 3.6,
+      "fiber_per_100g": 0
+    },
+    {
+      "id": "brown_rice",
+      "name": "Brown Rice (cooked)",
+      "carbs_per_100g": 23,
+      "protein_per_100g": 2.6,
+      "fat_per_100g": 0.9,
+      "fiber_per_100g": 1.8
+    },
+    {
+      "id": "olive_oil",
+      "name": "Extra Virgin Olive Oil",
+      "carbs_per_100g": 0,
+      "protein_per_100g": 0,
+      "fat_per_100g": 100,
+      "fiber_per_100g": 0
+    }
+  ]
+}"#,
+        r#"{
+  "recipes": [
+    {
+      "name": "Chicken Rice Bowl",
+      "ingredients": [
+        {"id": "chicken_breast", "grams": 120},
+        {"id": "brown_rice", "grams": 150},
+        {"id": "olive_oil", "grams": 10}
+      ]
+    }
+  ]
+}"#,
+    );
+    temp
+}
+
+fn optimize_snapshot(args: &[&str]) -> String {
+    let temp = create_optimize_catalog_dir();
+    let output = run_cmd(args, temp.path());
+    assert!(output.status.success());
+    format!(
+        "$ nutriterm {}\n{}",
+        args.join(" "),
+        strip_ansi_codes(&String::from_utf8_lossy(&output.stdout)).trim_end()
+    )
+}
+
+#[test]
+fn test_optimize_protein_and_calorie_limit() {
+    assert_snapshot!(
+        "protein_and_max_kcal",
+        optimize_snapshot(&["optimize", "bowl", "--protein", "45", "--max-kcal", "430"])
+    );
+}
+
+#[test]
+fn test_optimize_respects_locked_and_bounded_ingredients() {
+    assert_snapshot!(
+        "locked_and_bounded",
+        optimize_snapshot(&[
+            "optimize",
+            "bowl",
+            "--protein",
+            "45",
+            "--max-kcal",
+            "500",
+            "--lock",
+            "olive_oil",
+            "--min",
+            "brown_rice=160",
+        ])
+    );
+}
+
+#[test]
+fn test_optimize_infeasible_targets() {
+    let temp = create_optimize_catalog_dir();
+    let output = run_cmd(
+        &[
+            "optimize",
+            "bowl",
+            "--protein",
+            "80",
+            "--max-kcal",
+            "600",
+            "--lock",
+            "chicken_breast",
+        ],
+        temp.path(),
+    );
+    assert!(!output.status.success());
+    assert_snapshot!(String::from_utf8_lossy(&output.stderr));
+}
+
+#[test]
+fn test_optimize_requires_a_target() {
+    let temp = create_optimize_catalog_dir();
+    let output = run_cmd(&["optimize", "bowl"], temp.path());
+    assert!(!output.status.success());
+}
@@ -0,0 +1,97 @@
+use insta::assert_snapshot;
+use std::fs;
+
+mod common;
+use common::{example_catalog_dir, read, run_cmd, run_ok};
+
+#[test]
+fn test_pantry_shows_low_items_and_cookable_recipes() {
+    let temp = example_catalog_dir();
+    let mut transcript = String::new();
+    for args in [
+        vec!["pantry", "add", "oats", "500g", "--low", "200"],
+        vec!["pantry", "add", "brown rice", "150", "--low", "200"],
+        vec!["pantry", "add", "chicken_breast", "400"],
+        vec!["pantry", "add", "olive oil", "10"],
+        vec!["pantry"],
+    ] {
+        transcript.push_str(&format!("$ nutriterm {}\n", args.join(" ")));
+        transcript.push_str(&run_ok(&args, temp.path()));
+    }
+    assert_snapshot!(transcript);
+}
+
+#[test]
+fn test_cook_deducts_scaled_stock() {
+    let temp = example_catalog_dir();
+    run_ok(
+        &["pantry", "add", "oats", "500", "--low", "300"],
+        temp.path(),
+    );
+
+    let output = run_ok(&["cook", "porridge", "--factor", "2.5"], temp.path());
+    assert_snapshot!(output);
+
+    let pantry = read(&temp, "pantry.jsonc");
+    assert!(pantry.contains("\"grams\": 300"));
+    assert!(pantry.contains("\"low_grams\": 300"));
+}
+
+#[test]
+fn test_cook_refuses_when_stock_is_short() {
+    let temp = example_catalog_dir();
+    run_ok(&["pantry", "add", "chicken_breast", "100"], temp.path());
+    let before = read(&temp, "pantry.jsonc");
+
+    let output = run_cmd(&["cook", "Chicken Rice Bowl"], temp.path());
+    assert!(!output.status.success());
+    assert_snapshot!(String::from_utf8_lossy(&output.stderr));
+    let after = read(&temp, "pantry.jsonc");
+    assert_eq!(before, after);
+}
+
+#[test]
+fn test_cook_force_uses_up_stock() {
+    let temp = example_catalog_dir();
+    run_ok(&["pantry", "add", "chicken_breast", "100"], temp.path());
+
+    run_ok(&["cook", "Chicken Rice Bowl", "--force"], temp.path());
+    let pantry = read(&temp, "pantry.jsonc");
+    assert!(pantry.contains("\"grams\": 0"));
+    // Ingredients that were never stocked are recorded as empty
+    assert!(pantry.contains("\"id\": \"brown_rice\""));
+}
+
+#[test]
+fn test_pantry_keeps_comments() {
+    let temp = example_catalog_dir();
+    fs::write(
+        temp.path().join("pantry.jsonc"),
+        r#"{
+  "stock": [
+    // Big bag from the wholesale store
+    {"id": "oats", "grams": 1000}
+  ]
+}"#,
+    )
+    .unwrap();
+
+    run_ok(&["pantry", "add", "oats", "250"], temp.path());
+    let pantry = read(&temp, "pantry.jsonc");
+    assert!(pantry.contains("// Big bag from the wholesale store"));
+    assert!(pantry.contains("\"grams\": 1250"));
+}
+
+#[test]
+fn test_pantry_unknown_ingredient() {
+    let temp = example_catalog_dir();
+    fs::write(
+        temp.path().join("pantry.jsonc"),
+        r#"{"stock": [{"id": "oat", "grams": 100}]}"#,
+    )
+    .unwrap();
+
+    let output = run_cmd(&["pantry"], temp.path());
+    assert!(!output.status.success());
+    assert_snapshot!(String::from_utf8_lossy(&output.stderr));
+}
@@ -0,0 +1,106 @@
+use insta::assert_snapshot;
+use std::fs;
+
+mod common;
+use common::{example_catalog_dir, normalize_temp_paths, read, run_cmd, strip_ansi_codes};
+
+const PLANS: &str = r#"{
+  "days": [
+    {
+      "name": "monday",
+      "meals": [
+        {"meal": "Breakfast", "recipe": "Porridge"},
+        {"meal": "Lunch", "recipe": "Chicken Rice Bowl", "portions": 1.5},
+        {"meal": "Snack", "recipe": "Porridge", "grams": 40}
+      ]
+    },
+    {
+      "name": "tuesday",
+      "meals": [
+        {"meal": "Lunch", "recipe": "Chicken Rice Bowl"}
+      ]
+    }
+  ]
+}"#;
+
+fn create_plan_catalog_dir(plans: Option<&str>) -> tempfile::TempDir {
+    let temp = example_catalog_dir();
+    if let Some(plans) = plans {
+        fs::write(temp.path().join("plans.jsonc"), plans).unwrap();
+    }
+    temp
+}
+
+#[test]
+fn test_plan_shows_meals_and_day_total() {
+    let temp = create_plan_catalog_dir(Some(PLANS));
+    let output = run_cmd(&["plan", "mon"], temp.path());
+    assert!(output.status.success());
+    assert_snapshot!(format!(
+        "$ nutriterm plan mon\n{}",
+        strip_ansi_codes(&String::from_utf8_lossy(&output.stdout)).trim_end()
+    ));
+}
+
+#[test]
+fn test_plan_unknown_day_lists_days() {
+    let temp = create_plan_catalog_dir(Some(PLANS));
+    let output = run_cmd(&["plan", "friday"], temp.path());
+    assert!(!output.status.success());
+    assert_snapshot!(String::from_utf8_lossy(&output.stderr));
+}
+
+#[test]
+fn test_plan_unknown_recipe() {
+    let temp = create_plan_catalog_dir(Some(
+        r#"{"days": [{"name": "monday", "meals": [{"meal": "Lunch", "recipe": "Porige"}]}]}"#,
+    ));
+    let output = run_cmd(&["plan", "monday"], temp.path());
+    assert!(!output.status.success());
+    assert_snapshot!(String::from_utf8_lossy(&output.stderr));
+}
+
+#[test]
+fn test_plan_rejects_portions_and_grams_together() {
+    let temp = create_plan_catalog_dir(Some(
+        r#"{"days": [{"name": "monday", "meals": [{"meal": "Lunch", "recipe": "Porridge", "portions": 2, "grams": 100}]}]}"#,
+    ));
+    let output = run_cmd(&["plan", "monday"], temp.path());
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("Schema validation failed for plans.jsonc"));
+}
+
+#[test]
+fn test_plan_without_plans_file() {
+    let temp = create_plan_catalog_dir(None);
+    let output = run_cmd(&["plan", "monday"], temp.path());
+    assert!(!output.status.success());
+    assert_snapshot!(normalize_temp_paths(
+        &String::from_utf8_lossy(&output.stderr),
+        temp.path()
+    ));
+}
+
+#[test]
+fn test_plan_gram_portions_use_cooked_weight() {
+    let temp = create_plan_catalog_dir(Some(
+        r#"{"days": [{"name": "monday", "meals": [{"meal": "Lunch", "recipe": "Porridge", "grams": 100}]}]}"#,
+    ));
+    // 80 g of oats cook into 400 g of porridge, so 100 g is a quarter of the recipe
+    let recipes = read(&temp, "recipes.jsonc");
+    fs::write(
+        temp.path().join("recipes.jsonc"),
+        recipes.replace(
+            r#""name": "Porridge","#,
+            r#""name": "Porridge", "cooked_grams": 400,"#,
+        ),
+    )
+    .unwrap();
+
+    let output = run_cmd(&["plan", "monday"], temp.path());
+    assert!(output.status.success());
+    let stdout = strip_ansi_codes(&String::from_utf8_lossy(&output.stdout));
+    assert!(stdout.contains("Lunch: Porridge (100 g, ×0.25)"));
+    assert!(stdout.contains("20.0 g"));
+}
@@ -5,8 +5,8 @@ use tempfile::TempDir;
 
 mod common;
 use common::{
-    catalog_dir, create_catalog_files, format_test_snapshot, normalize_temp_paths, temp_dir,
-    write_files,
+    catalog_dir, create_catalog_files, example_catalog_dir, format_test_snapshot,
+    normalize_temp_paths, read, run_cmd, run_ok, temp_dir, write_files,
 };
 
 // Helper for recipe tests that need the standard catalog_dir with chicken-rice-bowl
@@ -821,3 +821,285 @@ fn test_search_many_matches_shows_truncated_list() {
     );
     assert_snapshot!("search_many_matches_truncated", snapshot_content);
 }
+
+#[test]
+fn test_search_multiple_matches_no_interactive() {
+    let temp_dir = temp_dir();
+    let catalog_dir = catalog_dir(&temp_dir, "no-interactive-test");
+    write_files(
+        &catalog_dir,
+        r#"{
+        "ingredients": [{
+            "id": "chicken_breast",
+            "name": "Chicken Breast",
+            "carbs_per_100g": 0,
+            "protein_per_100g": 31,
+            "fat_per_100g": 3.6,
+            "fiber_per_100g": 0
+        }]
+    }"#,
+        r#"{
+        "recipes": [
+            {
+                "name": "Chicken Rice Bowl",
+                "ingredients": [{"id": "chicken_breast", "grams": 150}]
+            },
+            {
+                "name": "Chicken Salad",
+                "ingredients": [{"id": "chicken_breast", "grams": 120}]
+            }
+        ]
+    }"#,
+    );
+
+    // Without a terminal the matches are listed; that --no-interactive also skips the
+    // picker on a terminal is covered by the unit tests of the recipe command
+    let assert = cargo_bin_cmd!("nutriterm")
+        .args(["recipe", "chicken", "--no-interactive"])
+        .current_dir(&catalog_dir)
+        .assert()
+        .success();
+
+    let stdout = String::from_utf8_lossy(&assert.get_output().stdout).to_string();
+    assert!(stdout.contains("Multiple recipes found for 'chicken' (2 matches)"));
+    assert!(stdout.contains("Please be more specific with your search term."));
+    assert!(!stdout.contains("Recipe:"));
+}
+
+#[test]
+fn test_recipe_scale_to_calories() {
+    let (_temp_dir, catalog_dir) = recipe_catalog_dir();
+
+    // User scales a recipe to hit a calorie target
+    let assert = cargo_bin_cmd!("nutriterm")
+        .args(["recipe", "Chicken Rice Bowl", "--scale-to", "600kcal"])
+        .current_dir(&catalog_dir)
+        .assert()
+        .success();
+
+    let output = assert.get_output();
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let snapshot_content = format_test_snapshot(
+        &["Chicken Rice Bowl"],
+        "Chicken Rice Bowl --scale-to 600kcal",
+        &stdout,
+    );
+    assert_snapshot!("scaled_to_calories", snapshot_content);
+}
+
+#[test]
+fn test_recipe_factor_kitchen_card() {
+    let (_temp_dir, catalog_dir) = recipe_catalog_dir();
+
+    // User prints the scaled weights for cooking
+    let assert = cargo_bin_cmd!("nutriterm")
+        .args(["recipe", "Chicken Rice Bowl", "--factor", "1.5", "--card"])
+        .current_dir(&catalog_dir)
+        .assert()
+        .success();
+
+    let output = assert.get_output();
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let snapshot_content = format_test_snapshot(
+        &["Chicken Rice Bowl"],
+        "Chicken Rice Bowl --factor 1.5 --card",
+        &stdout,
+    );
+    assert_snapshot!("kitchen_card_with_factor", snapshot_content);
+}
+
+#[test]
+fn test_recipe_scale_to_rejects_unknown_unit() {
+    let (_temp_dir, catalog_dir) = recipe_catalog_dir();
+
+    cargo_bin_cmd!("nutriterm")
+        .args(["recipe", "Chicken Rice Bowl", "--scale-to", "5lb"])
+        .current_dir(&catalog_dir)
+        .assert()
+        .failure();
+}
+
+#[test]
+fn test_recipe_scale_to_weight_uses_cooked_weight() {
+    let temp = example_catalog_dir();
+    // 80 g of oats cook into 400 g of porridge, so a 200 g bowl is half the recipe
+    let recipes = read(&temp, "recipes.jsonc");
+    fs::write(
+        temp.path().join("recipes.jsonc"),
+        recipes.replace(
+            r#""name": "Porridge","#,
+            r#""name": "Porridge", "cooked_grams": 400,"#,
+        ),
+    )
+    .unwrap();
+
+    let output = run_ok(&["recipe", "porridge", "--scale-to", "200g"], temp.path());
+    assert!(output.contains("Scaled ×0.50 to 200 g"));
+    assert!(output.contains("40.0 g"));
+}
+
+#[test]
+fn test_recipe_energy_breakdown() {
+    let (_temp_dir, catalog_dir) = recipe_catalog_dir();
+
+    // User checks where the calories and protein come from
+    let assert = cargo_bin_cmd!("nutriterm")
+        .args(["recipe", "Chicken Rice Bowl", "--breakdown"])
+        .current_dir(&catalog_dir)
+        .assert()
+        .success();
+
+    let output = assert.get_output();
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let snapshot_content = format_test_snapshot(
+        &["Chicken Rice Bowl"],
+        "Chicken Rice Bowl --breakdown",
+        &stdout,
+    );
+    assert_snapshot!("energy_breakdown", snapshot_content);
+}
+
+#[test]
+fn test_recipe_per_100g_uses_cooked_weight() {
+    let temp = temp_dir();
+    write_files(
+        temp.path(),
+        r#"{
+  "ingredients": [
+    {
+      "id": "lentils",
+      "name": "Red Lentils (dry)",
+      "carbs_per_100g": 49,
+      "protein_per_100g": 24,
+      "fat_per_100g": 1.1,
+      "fiber_per_100g": 11
+    }
+  ]
+}"#,
+        r#"{
+  "recipes": [
+    {
+      "name": "Lentil Soup",
+      "cooked_grams": 500,
+      "ingredients": [{"id": "lentils", "grams": 200}]
+    },
+    {
+      "name": "Lentil Salad",
+      "ingredients": [{"id": "lentils", "grams": 200}]
+    }
+  ]
+}"#,
+    );
+
+    // Soup: 200 g dry lentils cook into 500 g, so per 100 g is 2/5 of the lentils' values
+    let assert = cargo_bin_cmd!("nutriterm")
+        .args(["recipe", "Lentil Soup", "--per-100g"])
+        .current_dir(temp.path())
+        .assert()
+        .success();
+    let stdout = String::from_utf8_lossy(&assert.get_output().stdout).into_owned();
+    assert_snapshot!(
+        "per_100g_cooked",
+        format_test_snapshot(
+            &["Lentil Soup", "Lentil Salad"],
+            "Lentil Soup --per-100g",
+            &stdout
+        )
+    );
+
+    // Without a cooked weight the raw ingredient weight is used
+    let assert = cargo_bin_cmd!("nutriterm")
+        .args(["recipe", "Lentil Salad", "--per-100g"])
+        .current_dir(temp.path())
+        .assert()
+        .success();
+    let stdout = String::from_utf8_lossy(&assert.get_output().stdout);
+    assert!(stdout.contains("Per 100 g"));
+    assert!(stdout.contains("24.0 g"));
+}
+
+#[test]
+fn test_recipe_text_formats() {
+    let (_temp_dir, catalog_dir) = recipe_catalog_dir();
+
+    // User copies the numbers into a wiki page, a spreadsheet and a note
+    for format in ["markdown", "csv", "plain"] {
+        let assert = cargo_bin_cmd!("nutriterm")
+            .args([
+                "recipe",
+                "Chicken Rice Bowl",
+                "--format",
+                format,
+                "--per-100g",
+            ])
+            .current_dir(&catalog_dir)
+            .assert()
+            .success();
+
+        let output = assert.get_output();
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let snapshot_content = format_test_snapshot(
+            &["Chicken Rice Bowl"],
+            &format!("Chicken Rice Bowl --format {} --per-100g", format),
+            &stdout,
+        );
+        assert_snapshot!(format!("format_{}", format), snapshot_content);
+    }
+}
+
+#[test]
+fn test_recipe_json_format_is_unrounded() {
+    let (_temp_dir, catalog_dir) = recipe_catalog_dir();
+
+    let assert = cargo_bin_cmd!("nutriterm")
+        .args([
+            "recipe",
+            "Chicken Rice Bowl",
+            "--format",
+            "json",
+            "--factor",
+            "1.5",
+        ])
+        .current_dir(&catalog_dir)
+        .assert()
+        .success();
+
+    let json: serde_json::Value = serde_json::from_slice(&assert.get_output().stdout).unwrap();
+    assert_eq!(json["name"], "Chicken Rice Bowl");
+    assert_eq!(json["factor"], 1.5);
+    let ingredients = json["ingredients"].as_array().unwrap();
+    let summed: f64 = ingredients
+        .iter()
+        .map(|i| i["calories"].as_f64().unwrap())
+        .sum();
+    assert!(ingredients.iter().all(|i| i["id"].is_string()));
+    assert!((json["total"]["calories"].as_f64().unwrap() - summed).abs() < 1e-9);
+    // Rounded values would have at most one decimal
+    assert!(
+        ingredients
+            .iter()
+            .any(|i| (i["calories"].as_f64().unwrap() * 10.0).fract().abs() > 1e-9)
+    );
+}
+
+#[test]
+fn test_recipe_machine_formats_fail_without_single_match() {
+    let temp = example_catalog_dir();
+
+    // A script gets an error instead of prose it cannot parse
+    let output = run_cmd(&["recipe", "pizza", "--format", "json"], temp.path());
+    assert!(!output.status.success());
+    assert!(output.stdout.is_empty());
+    assert_eq!(
+        String::from_utf8_lossy(&output.stderr),
+        "Error: No recipe matches 'pizza'\n"
+    );
+
+    let output = run_cmd(&["recipe", "chicken", "--format", "csv"], temp.path());
+    assert!(!output.status.success());
+    assert!(output.stdout.is_empty());
+    assert!(
+        String::from_utf8_lossy(&output.stderr)
+            .starts_with("Error: Multiple recipes match 'chicken'")
+    );
+}
@@ -0,0 +1,147 @@
+use std::fs;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpStream;
+use std::process::{Child, ChildStdout, Command, Stdio};
+
+mod common;
+use common::{EXAMPLE_RECIPES, example_catalog_dir};
+
+/// A running `nutriterm serve`, stopped when dropped
+struct TestServer {
+    child: Child,
+    address: String,
+    /// Kept open so the server's log lines have somewhere to go
+    _stdout: BufReader<ChildStdout>,
+}
+
+impl TestServer {
+    fn start(dir: &std::path::Path) -> Self {
+        let mut child = Command::new(assert_cmd::cargo_bin!("nutriterm"))
+            .args(["serve", "--port", "0"])
+            .current_dir(dir)
+            .stdout(Stdio::piped())
+            .spawn()
+            .unwrap();
+        let mut stdout = BufReader::new(child.stdout.take().unwrap());
+        let mut line = String::new();
+        stdout.read_line(&mut line).unwrap();
+        let address = line
+            .split("http://")
+            .nth(1)
+            .and_then(|rest| rest.split('/').next())
+            .unwrap_or_else(|| panic!("no address in {:?}", line))
+            .to_string();
+        TestServer {
+            child,
+            address,
+            _stdout: stdout,
+        }
+    }
+
+    /// Status code and body of a GET request
+    fn get(&self, path: &str) -> (u16, String) {
+        let mut stream = TcpStream::connect(&self.address).unwrap();
+        write!(
+            stream,
+            "GET {} HTTP/1.1\r\nHost: {}\r\nConnection: close\r\n\r\n",
+            path, self.address
+        )
+        .unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        let (head, body) = response.split_once("\r\n\r\n").unwrap();
+        let status = head.split(' ').nth(1).unwrap().parse().unwrap();
+        (status, body.to_string())
+    }
+
+    fn get_json(&self, path: &str) -> (u16, serde_json::Value) {
+        let (status, body) = self.get(path);
+        (status, serde_json::from_str(&body).unwrap())
+    }
+}
+
+impl Drop for TestServer {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+#[test]
+fn test_serve_kitchen_reference_and_api() {
+    let temp = example_catalog_dir();
+    let server = TestServer::start(temp.path());
+
+    let (status, html) = server.get("/");
+    assert_eq!(status, 200);
+    assert!(html.contains("<h2>Chicken Rice Bowl</h2>"));
+    assert!(html.contains("/api/generation"));
+
+    let (status, recipes) = server.get_json("/api/recipes");
+    assert_eq!(status, 200);
+    assert_eq!(recipes[1]["name"], "Chicken Rice Bowl");
+    assert_eq!(recipes[1]["tags"][0], "lunch");
+    assert_eq!(recipes[2]["weight_grams"], 205.0);
+
+    let (status, recipe) = server.get_json("/api/recipes/Chicken%20Rice%20Bowl?scale=2");
+    assert_eq!(status, 200);
+    assert_eq!(recipe["factor"], 2.0);
+    assert_eq!(recipe["ingredients"][0]["id"], "chicken_breast");
+    assert_eq!(recipe["ingredients"][0]["weight_grams"], 300.0);
+    assert_eq!(recipe["ingredients"][0]["protein_grams"], 93.0);
+    assert_eq!(recipe["total"]["weight_grams"], 520.0);
+
+    // Search terms work as on the command line
+    let (_, recipe) = server.get_json("/api/recipes/plain");
+    assert_eq!(recipe["name"], "Plain Chicken");
+    assert_eq!(recipe["factor"], 1.0);
+
+    let (status, ingredients) = server.get_json("/api/ingredients");
+    assert_eq!(status, 200);
+    assert_eq!(ingredients[0]["id"], "chicken_breast");
+    assert_eq!(ingredients[1]["per_100g"]["net_carbs_grams"], 23.0);
+}
+
+#[test]
+fn test_serve_reports_errors_as_json() {
+    let temp = example_catalog_dir();
+    let server = TestServer::start(temp.path());
+
+    let (status, body) = server.get_json("/api/recipes/chicken");
+    assert_eq!(status, 404);
+    assert!(
+        body["error"]
+            .as_str()
+            .unwrap()
+            .starts_with("Multiple recipes match 'chicken'")
+    );
+
+    let (status, body) = server.get_json("/api/recipes/plain?scale=0");
+    assert_eq!(status, 400);
+    assert_eq!(
+        body["error"],
+        "Invalid scale: expected a positive number, got '0'"
+    );
+
+    let (status, _) = server.get_json("/api/nothing");
+    assert_eq!(status, 404);
+}
+
+#[test]
+fn test_serve_reloads_when_files_change() {
+    let temp = example_catalog_dir();
+    let server = TestServer::start(temp.path());
+
+    let (_, generation) = server.get_json("/api/generation");
+    assert_eq!(generation["generation"], 0);
+
+    fs::write(
+        temp.path().join("recipes.jsonc"),
+        EXAMPLE_RECIPES.replace("\"grams\": 200", "\"grams\": 250"),
+    )
+    .unwrap();
+    let (_, recipe) = server.get_json("/api/recipes/plain");
+    assert_eq!(recipe["total"]["weight_grams"], 255.0);
+    let (_, generation) = server.get_json("/api/generation");
+    assert_eq!(generation["generation"], 1);
+}
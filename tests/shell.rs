@@ -0,0 +1,70 @@
+use assert_cmd::cargo::cargo_bin_cmd;
+use std::fs;
+use std::io::{BufRead, BufReader, Write};
+use std::process::{Command, Stdio};
+
+mod common;
+use common::{EXAMPLE_RECIPES, example_catalog_dir, strip_ansi_codes};
+
+#[test]
+fn test_shell_runs_commands_against_loaded_catalog() {
+    let temp = example_catalog_dir();
+
+    // A mistyped command reports an error and the session carries on
+    let assert = cargo_bin_cmd!("nutriterm")
+        .arg("shell")
+        .current_dir(temp.path())
+        .write_stdin(
+            "recipe bowl\nfrobnicate\nscale \"Plain Chicken\" 1.5 --card\ningredient rice\nexit\nrecipe bowl\n",
+        )
+        .assert()
+        .success();
+
+    let output = assert.get_output();
+    let stdout = strip_ansi_codes(&String::from_utf8_lossy(&output.stdout));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert_eq!(stdout.matches("Recipe: Chicken Rice Bowl").count(), 1);
+    assert!(stdout.contains("Plain Chicken (×1.50)"));
+    assert!(stdout.contains("300.0 g  Chicken Breast"));
+    assert!(stdout.contains("Ingredient: Brown Rice"));
+    assert!(stderr.contains("unrecognized subcommand 'frobnicate'"));
+}
+
+#[test]
+fn test_shell_reloads_when_files_change() {
+    let temp = example_catalog_dir();
+
+    let mut child = Command::new(assert_cmd::cargo_bin!("nutriterm"))
+        .arg("shell")
+        .current_dir(temp.path())
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+    let mut stdin = child.stdin.take().unwrap();
+    let mut stdout = BufReader::new(child.stdout.take().unwrap());
+
+    // Wait until the first command has run on the catalog as loaded at start
+    writeln!(stdin, "recipe porridge").unwrap();
+    let mut line = String::new();
+    while !strip_ansi_codes(&line).contains("Total") {
+        line.clear();
+        assert!(stdout.read_line(&mut line).unwrap() > 0);
+    }
+    assert!(line.contains("80.0 g"));
+
+    fs::write(
+        temp.path().join("recipes.jsonc"),
+        EXAMPLE_RECIPES.replace("\"grams\": 80", "\"grams\": 100"),
+    )
+    .unwrap();
+    writeln!(stdin, "recipe porridge").unwrap();
+    drop(stdin);
+
+    let mut rest = String::new();
+    std::io::Read::read_to_string(&mut stdout, &mut rest).unwrap();
+    assert!(child.wait().unwrap().success());
+    let rest = strip_ansi_codes(&rest);
+    assert!(rest.contains("Catalog files changed, reloaded"));
+    assert!(rest.contains("100.0 g"));
+}
@@ -0,0 +1,120 @@
+use insta::assert_snapshot;
+
+mod common;
+use common::{run_cmd, run_ok, temp_dir, write_files};
+
+fn create_shop_catalog_dir() -> tempfile::TempDir {
+    let temp = temp_dir();
+    write_files(
+        temp.path(),
+        r#"{
+  "ingredients": [
+    {
+      "id": "chicken_breast",
+      "name": "Chicken Breast (skinless)",
+      "carbs_per_100g": 0,
+      "protein_per_100g": 31,
+      "fat_per_100g": 3.6,
+      "fiber_per_100g": 0,
+      "aisle": "Meat",
+      "package_grams": 400
+    },
+    {
+      "id": "brown_rice",
+      "name": "Brown Rice (cooked)",
+      "carbs_per_100g": 23,
+      "protein_per_100g": 2.6,
+      "fat_per_100g": 0.9,
+      "fiber_per_100g": 1.8,
+      "aisle": "Pantry"
+    },
+    {
+      "id": "oats",
+      "name": "Rolled Oats",
+      "carbs_per_100g": 56,
+      "protein_per_100g": 13,
+      "fat_per_100g": 7,
+      "fiber_per_100g": 10,
+      "aisle": "Pantry",
+      "package_grams": 500
+    },
+    {
+      "id": "salt",
+      "name": "Salt & Pepper <mix>",
+      "carbs_per_100g": 0,
+      "protein_per_100g": 0,
+      "fat_per_100g": 0,
+      "fiber_per_100g": 0
+    }
+  ]
+}"#,
+        r#"{
+  "recipes": [
+    {
+      "name": "Porridge",
+      "ingredients": [{"id": "oats", "grams": 80}]
+    },
+    {
+      "name": "Chicken Rice Bowl",
+      "ingredients": [
+        {"id": "chicken_breast", "grams": 150},
+        {"id": "brown_rice", "grams": 100},
+        {"id": "salt", "grams": 2}
+      ]
+    },
+    {
+      "name": "Chicken Oat Bake",
+      "ingredients": [
+        {"id": "chicken_breast", "grams": 200},
+        {"id": "oats", "grams": 50}
+      ]
+    }
+  ]
+}"#,
+    );
+    temp
+}
+
+fn shop(args: &[&str], dir: &std::path::Path) -> String {
+    let mut full_args = vec!["shop"];
+    full_args.extend(args);
+    run_ok(&full_args, dir)
+}
+
+#[test]
+fn test_shop_adds_up_ingredients_by_aisle() {
+    let temp = create_shop_catalog_dir();
+    let output = shop(&["rice bowl x2", "oat bake", "porridge×3"], temp.path());
+    // Chicken: 2 × 150 + 200 = 500 g → 2 packages of 400 g
+    // Oats: 50 + 3 × 80 = 290 g → 1 package of 500 g
+    assert_snapshot!(format!(
+        "$ nutriterm shop \"rice bowl x2\" \"oat bake\" porridge×3\n{}",
+        output
+    ));
+}
+
+#[test]
+fn test_shop_markdown_checklist() {
+    let temp = create_shop_catalog_dir();
+    let output = shop(&["Porridge", "--format", "markdown"], temp.path());
+    assert_snapshot!(output);
+}
+
+#[test]
+fn test_shop_html_escapes_names() {
+    let temp = create_shop_catalog_dir();
+    let output = shop(&["Chicken Rice Bowl", "--format", "html"], temp.path());
+    assert!(output.contains("<h2>Other</h2>"));
+    assert!(output.contains("Salt &amp; Pepper &lt;mix&gt;: 2.0 g"));
+    assert!(output.contains(
+        "<input type=\"checkbox\"> Chicken Breast (skinless): 150.0 g (1 × 400 g package)"
+    ));
+}
+
+#[test]
+fn test_shop_ambiguous_recipe_fails() {
+    let temp = create_shop_catalog_dir();
+    let output = run_cmd(&["shop", "chicken x2"], temp.path());
+    assert!(!output.status.success());
+    assert_snapshot!(String::from_utf8_lossy(&output.stderr));
+}
@@ -0,0 +1,144 @@
+use insta::assert_snapshot;
+use std::fs;
+
+mod common;
+use common::{run_cmd, run_ok, temp_dir, write_files};
+
+const TARGETS: &str = r#"{
+  // Shared by everyone
+  "daily": {
+    "protein": {"min": 120},
+    "carbs": {"max": 200},
+    "calories": {"min": 1800, "max": 2200}
+  },
+  "people": {
+    "alex": {
+      "protein": {"min": 150},
+      "carbs": {"max": 50}
+    }
+  }
+}"#;
+
+fn create_targets_catalog_dir(targets: Option<&str>) -> tempfile::TempDir {
+    let temp = temp_dir();
+    write_files(
+        temp.path(),
+        r#"{
+  "ingredients": [
+    {
+      "id": "chicken_breast",
+      "name": "Chicken Breast (skinless)",
+      "carbs_per_100g": 0,
+      "protein_per_100g": 31,
+      "fat_per_100g": 3.6,
+      "fiber_per_100g": 0
+    },
+    {
+      "id": "brown_rice",
+      "name": "Brown Rice (cooked)",
+      "carbs_per_100g": 23,
+      "protein_per_100g": 2.6,
+      "fat_per_100g": 0.9,
+      "fiber_per_100g": 1.8
+    }
+  ]
+}"#,
+        r#"{
+  "recipes": [
+    {
+      "name": "Chicken Rice Bowl",
+      "ingredients": [
+        {"id": "chicken_breast", "grams": 150},
+        {"id": "brown_rice", "grams": 300}
+      ]
+    }
+  ]
+}"#,
+    );
+    fs::write(
+        temp.path().join("plans.jsonc"),
+        r#"{"days": [{"name": "monday", "meals": [
+  {"meal": "Lunch", "recipe": "Chicken Rice Bowl"},
+  {"meal": "Dinner", "recipe": "Chicken Rice Bowl"}
+]}]}"#,
+    )
+    .unwrap();
+    if let Some(targets) = targets {
+        fs::write(temp.path().join("targets.jsonc"), targets).unwrap();
+    }
+    temp
+}
+
+fn stdout_of(args: &[&str], dir: &std::path::Path) -> String {
+    run_ok(args, dir).trim_end().to_string()
+}
+
+#[test]
+fn test_recipe_shows_share_of_daily_targets() {
+    let temp = create_targets_catalog_dir(Some(TARGETS));
+    let output = stdout_of(&["recipe", "Chicken Rice Bowl"], temp.path());
+    assert_snapshot!(format!(
+        "$ nutriterm recipe \"Chicken Rice Bowl\"\n{}",
+        output
+    ));
+}
+
+#[test]
+fn test_recipe_without_targets_file_has_no_target_row() {
+    let temp = create_targets_catalog_dir(None);
+    let output = stdout_of(&["recipe", "Chicken Rice Bowl"], temp.path());
+    assert!(!output.contains("% of daily target"));
+}
+
+#[test]
+fn test_plan_shows_remaining_budget_for_person() {
+    let temp = create_targets_catalog_dir(Some(TARGETS));
+    let output = stdout_of(&["plan", "monday", "--person", "alex"], temp.path());
+    let progress = &output[output.find("Daily targets").unwrap()..];
+    assert_snapshot!(format!(
+        "$ nutriterm plan monday --person alex\n...\n{}",
+        progress
+    ));
+}
+
+#[test]
+fn test_person_overrides_only_their_nutrients() {
+    let temp = create_targets_catalog_dir(Some(TARGETS));
+    let shared = stdout_of(&["plan", "monday"], temp.path());
+    let alex = stdout_of(&["plan", "monday", "--person", "alex"], temp.path());
+
+    assert!(shared.contains("≥ 120.0 g") && shared.contains("≤ 200.0 g"));
+    assert!(alex.contains("≥ 150.0 g") && alex.contains("≤ 50.0 g"));
+    // Calories come from the shared targets for both
+    assert!(alex.contains("1,800 kcal – 2,200 kcal"));
+}
+
+#[test]
+fn test_unknown_person_suggests_closest() {
+    let temp = create_targets_catalog_dir(Some(TARGETS));
+    let output = run_cmd(&["plan", "monday", "--person", "alx"], temp.path());
+    assert!(!output.status.success());
+    assert_snapshot!(String::from_utf8_lossy(&output.stderr));
+}
+
+#[test]
+fn test_day_shows_remaining_budget() {
+    let temp = create_targets_catalog_dir(Some(TARGETS));
+    let diary = r#"{"entries": [
+  {"date": "2026-03-02", "recipe": "Chicken Rice Bowl", "grams": 450, "carbs": 69, "protein": 54.3, "fat": 8.1, "fiber": 5.4, "calories": 566.1}
+]}"#;
+    fs::write(temp.path().join("diary.jsonc"), diary).unwrap();
+
+    let output = stdout_of(&["day", "2026-03-02"], temp.path());
+    assert!(output.contains("▼ 65.7 g to go"));
+    assert!(output.contains("✓ 131.0 g left"));
+}
+
+#[test]
+fn test_day_without_entries_shows_whole_budget() {
+    let temp = create_targets_catalog_dir(Some(TARGETS));
+
+    let output = stdout_of(&["day", "2026-03-02"], temp.path());
+    assert!(output.contains("Nothing logged on 2026-03-02."));
+    assert!(output.contains("▼ 120.0 g to go"));
+}
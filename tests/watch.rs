@@ -0,0 +1,160 @@
+use std::fs;
+use std::io::{BufRead, BufReader};
+use std::process::{Child, ChildStdout, Command, Stdio};
+
+mod common;
+use common::{EXAMPLE_RECIPES, example_catalog_dir, strip_ansi_codes};
+
+fn spawn_watch(dir: &std::path::Path, args: &[&str]) -> (Child, BufReader<ChildStdout>) {
+    let mut child = Command::new(assert_cmd::cargo_bin!("nutriterm"))
+        .args(args)
+        .current_dir(dir)
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+    let stdout = BufReader::new(child.stdout.take().unwrap());
+    (child, stdout)
+}
+
+/// Read redraws until one contains `expected`, returning that redraw
+///
+/// A save can be noticed halfway through writing, so a redraw may be skipped over.
+fn wait_for(stdout: &mut BufReader<ChildStdout>, expected: &str) -> String {
+    loop {
+        let mut redraw = String::new();
+        while !redraw.contains("Watching") {
+            assert!(stdout.read_line(&mut redraw).unwrap() > 0);
+        }
+        let redraw = strip_ansi_codes(&redraw);
+        if redraw.contains(expected) {
+            return redraw;
+        }
+    }
+}
+
+#[test]
+fn test_recipe_watch_redraws_and_shows_errors() {
+    let temp = example_catalog_dir();
+    let (mut child, mut stdout) = spawn_watch(temp.path(), &["recipe", "porridge", "--watch"]);
+
+    let first = wait_for(&mut stdout, "80.0 g");
+    assert!(
+        first.contains("Watching recipes.jsonc, ingredients.jsonc and targets.jsonc for changes")
+    );
+
+    // A broken save is reported and watching goes on
+    fs::write(temp.path().join("recipes.jsonc"), "{ \"recipes\": [").unwrap();
+    wait_for(&mut stdout, "Error: Invalid JSONC syntax in recipes.jsonc");
+
+    fs::write(
+        temp.path().join("recipes.jsonc"),
+        EXAMPLE_RECIPES.replace("\"grams\": 80", "\"grams\": 120"),
+    )
+    .unwrap();
+    wait_for(&mut stdout, "120.0 g");
+
+    child.kill().unwrap();
+    child.wait().unwrap();
+}
+
+#[test]
+fn test_recipe_watch_redraws_when_targets_change() {
+    let temp = example_catalog_dir();
+    let (mut child, mut stdout) = spawn_watch(temp.path(), &["recipe", "porridge", "--watch"]);
+
+    let first = wait_for(&mut stdout, "80.0 g");
+    assert!(!first.contains("% of daily target"));
+
+    // Setting up targets while watching adds the target row
+    fs::write(
+        temp.path().join("targets.jsonc"),
+        r#"{"daily": {"protein": {"min": 120}}}"#,
+    )
+    .unwrap();
+    wait_for(&mut stdout, "% of daily target");
+
+    child.kill().unwrap();
+    child.wait().unwrap();
+}
+
+#[test]
+fn test_kitchen_ref_watch_regenerates_file() {
+    let temp = example_catalog_dir();
+    let output = temp.path().join("kitchen.html");
+    let (mut child, mut stdout) = spawn_watch(
+        temp.path(),
+        &["kitchen-ref", "--watch", "-o", "kitchen.html"],
+    );
+
+    wait_for(&mut stdout, "Wrote kitchen.html");
+    assert!(
+        fs::read_to_string(&output)
+            .unwrap()
+            .contains("80.0 g</span> Rolled Oats")
+    );
+
+    fs::write(temp.path().join("recipes.jsonc"), "{ \"recipes\": [").unwrap();
+    wait_for(&mut stdout, "Error: Invalid JSONC syntax");
+    let html = fs::read_to_string(&output).unwrap();
+    assert!(html.contains("<p>The catalog could not be loaded:</p>"));
+
+    fs::write(
+        temp.path().join("recipes.jsonc"),
+        EXAMPLE_RECIPES.replace("\"grams\": 80", "\"grams\": 120"),
+    )
+    .unwrap();
+    wait_for(&mut stdout, "Wrote kitchen.html");
+    assert!(
+        fs::read_to_string(&output)
+            .unwrap()
+            .contains("120.0 g</span> Rolled Oats")
+    );
+
+    child.kill().unwrap();
+    child.wait().unwrap();
+}
+
+#[test]
+fn test_kitchen_ref_watch_rereads_template() {
+    let temp = example_catalog_dir();
+    fs::write(
+        temp.path().join("cards.html"),
+        "<body class=\"cards\">{{recipes}}</body>",
+    )
+    .unwrap();
+    let output = temp.path().join("kitchen.html");
+    let (mut child, mut stdout) = spawn_watch(
+        temp.path(),
+        &[
+            "kitchen-ref",
+            "--watch",
+            "-o",
+            "kitchen.html",
+            "--template",
+            "cards.html",
+        ],
+    );
+
+    let first = wait_for(&mut stdout, "Wrote kitchen.html");
+    assert!(first.contains("Watching recipes.jsonc, ingredients.jsonc and cards.html for changes"));
+    assert!(
+        fs::read_to_string(&output)
+            .unwrap()
+            .starts_with("<body class=\"cards\">")
+    );
+
+    fs::write(
+        temp.path().join("cards.html"),
+        "<body class=\"list\">{{recipes}}</body>",
+    )
+    .unwrap();
+    wait_for(&mut stdout, "Wrote kitchen.html");
+    assert!(
+        fs::read_to_string(&output)
+            .unwrap()
+            .starts_with("<body class=\"list\">")
+    );
+
+    child.kill().unwrap();
+    child.wait().unwrap();
+}